@@ -0,0 +1,231 @@
+use svg::node::element::path::Data;
+use svg::node::element::{Group, Line, Path, Rectangle, Text};
+use svg::Document;
+
+const WIDTH: f64 = 800.;
+const MARGIN: f64 = 60.;
+const LEGEND_WIDTH: f64 = 200.;
+const ROW_HEIGHT: f64 = 22.;
+const LABEL_WIDTH: f64 = 120.;
+const FONT_SIZE: f64 = 12.;
+
+const COLORS: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+
+/// A named series of values of a chart.
+pub struct Series {
+    pub name: String,
+    /// One value per point of the chart, NaN if missing.
+    pub values: Vec<f64>,
+}
+
+fn text(x: f64, y: f64, anchor: &str, content: impl Into<String>) -> Text {
+    Text::new()
+        .add(svg::node::Text::new(content))
+        .set("x", x)
+        .set("y", y)
+        .set("font-family", "sans-serif")
+        .set("font-size", FONT_SIZE)
+        .set("text-anchor", anchor)
+}
+
+fn line(x1: f64, y1: f64, x2: f64, y2: f64, color: &str) -> Line {
+    Line::new()
+        .set("x1", x1)
+        .set("y1", y1)
+        .set("x2", x2)
+        .set("y2", y2)
+        .set("stroke", color)
+}
+
+// powers of 10 covering `min..=max`, both positive
+fn decades(min: f64, max: f64) -> (i32, i32) {
+    let low = min.log10().floor() as i32;
+    let high = (max.log10().ceil() as i32).max(low + 1);
+    (low, high)
+}
+
+fn format_power(exponent: i32) -> String {
+    match exponent {
+        0 => "1".to_string(),
+        1 => "10".to_string(),
+        _ => format!("1e{}", exponent),
+    }
+}
+
+/// Line chart of every series over `x` with logarithmic axes, e.g. time over array size.
+pub fn line_chart(
+    title: &str,
+    x_label: &str,
+    y_label: &str,
+    x: &[f64],
+    series: &[Series],
+) -> Document {
+    // the legend lists every series
+    let height = (MARGIN * 2. + series.len() as f64 * ROW_HEIGHT).max(500.);
+    let (left, top) = (MARGIN + 20., MARGIN);
+    let (right, bottom) = (WIDTH - LEGEND_WIDTH, height - MARGIN);
+    let positive = |values: &mut dyn Iterator<Item = f64>| {
+        values
+            .filter(|value| *value > 0.0 && value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            })
+    };
+    let (x_min, x_max) = positive(&mut x.iter().copied());
+    let (y_min, y_max) = positive(
+        &mut series
+            .iter()
+            .flat_map(|series| series.values.iter().copied()),
+    );
+    let (x_low, x_high) = match x_min.is_finite() {
+        true => decades(x_min, x_max),
+        false => (0, 1),
+    };
+    let (y_low, y_high) = match y_min.is_finite() {
+        true => decades(y_min, y_max),
+        false => (0, 1),
+    };
+    let scale_x = |value: f64| {
+        left + (value.log10() - x_low as f64) / (x_high - x_low) as f64 * (right - left)
+    };
+    let scale_y = |value: f64| {
+        bottom - (value.log10() - y_low as f64) / (y_high - y_low) as f64 * (bottom - top)
+    };
+
+    let mut axes = Group::new()
+        .add(line(left, bottom, right, bottom, "black"))
+        .add(line(left, top, left, bottom, "black"));
+    for exponent in x_low..=x_high {
+        let position = scale_x(10f64.powi(exponent));
+        axes = axes
+            .add(line(position, top, position, bottom, "#dddddd"))
+            .add(text(
+                position,
+                bottom + 16.,
+                "middle",
+                format_power(exponent),
+            ));
+    }
+    for exponent in y_low..=y_high {
+        let position = scale_y(10f64.powi(exponent));
+        axes = axes
+            .add(line(left, position, right, position, "#dddddd"))
+            .add(text(
+                left - 6.,
+                position + 4.,
+                "end",
+                format_power(exponent),
+            ));
+    }
+    axes = axes
+        .add(text((left + right) / 2., height - 20., "middle", x_label))
+        .add(text(0., 0., "middle", y_label).set(
+            "transform",
+            format!("translate(16 {}) rotate(-90)", (top + bottom) / 2.),
+        ));
+
+    let mut document = Document::new()
+        .set("viewBox", (0, 0, WIDTH, height))
+        .set("width", WIDTH)
+        .set("height", height)
+        .add(text(WIDTH / 2., 30., "middle", title).set("font-size", FONT_SIZE + 4.))
+        .add(axes);
+    for (i, series) in series.iter().enumerate() {
+        let color = COLORS[i % COLORS.len()];
+        // colors repeat with another dash pattern
+        let dashes = ["none", "6 3", "2 2"][i / COLORS.len() % 3];
+        // a missing value interrupts the line
+        let mut data = Data::new();
+        let mut drawing = false;
+        for (&x, &y) in x.iter().zip(&series.values) {
+            if y > 0.0 && y.is_finite() && x > 0.0 {
+                let point = (scale_x(x), scale_y(y));
+                data = match drawing {
+                    true => data.line_to(point),
+                    false => data.move_to(point),
+                };
+                drawing = true;
+            } else {
+                drawing = false;
+            }
+        }
+        let legend_y = top + i as f64 * ROW_HEIGHT;
+        document = document
+            .add(
+                Path::new()
+                    .set("fill", "none")
+                    .set("stroke", color)
+                    .set("stroke-width", 2)
+                    .set("stroke-dasharray", dashes)
+                    .set("d", data),
+            )
+            .add(
+                line(right + 20., legend_y, right + 40., legend_y, color)
+                    .set("stroke-width", 2)
+                    .set("stroke-dasharray", dashes),
+            )
+            .add(text(
+                right + 46.,
+                legend_y + 4.,
+                "start",
+                series.name.as_str(),
+            ));
+    }
+    document
+}
+
+/// Horizontal bar chart with one bar per label, e.g. the mean time of every sort.
+///
+/// Missing (NaN) values are drawn as empty rows.
+pub fn bar_chart(title: &str, unit: &str, labels: &[String], values: &[f64]) -> Document {
+    let top = MARGIN;
+    let height = top + labels.len() as f64 * ROW_HEIGHT + MARGIN;
+    let (left, right) = (LABEL_WIDTH + 10., WIDTH - MARGIN - 40.);
+    let max = values
+        .iter()
+        .copied()
+        .filter(|value| value.is_finite())
+        .fold(0.0, f64::max);
+
+    let mut document = Document::new()
+        .set("viewBox", (0, 0, WIDTH, height))
+        .set("width", WIDTH)
+        .set("height", height)
+        .add(text(WIDTH / 2., 30., "middle", title).set("font-size", FONT_SIZE + 4.))
+        .add(line(left, top, left, height - MARGIN, "black"));
+    for (i, (label, &value)) in labels.iter().zip(values).enumerate() {
+        let y = top + i as f64 * ROW_HEIGHT;
+        document = document.add(text(
+            left - 6.,
+            y + ROW_HEIGHT / 2. + 4.,
+            "end",
+            label.as_str(),
+        ));
+        if !value.is_finite() {
+            continue;
+        }
+        let width = match max > 0.0 {
+            true => value / max * (right - left),
+            false => 0.0,
+        };
+        document = document
+            .add(
+                Rectangle::new()
+                    .set("x", left)
+                    .set("y", y + 3.)
+                    .set("width", width)
+                    .set("height", ROW_HEIGHT - 6.)
+                    .set("fill", COLORS[i % COLORS.len()]),
+            )
+            .add(text(
+                left + width + 4.,
+                y + ROW_HEIGHT / 2. + 4.,
+                "start",
+                format!("{:.3e} {}", value, unit),
+            ));
+    }
+    document
+}
@@ -0,0 +1,456 @@
+use crate::common::{outln, Io, LineReader, RECORDING_HEADER};
+use crate::dataset;
+use crate::distribution::Distribution;
+use crate::editor::LineEditor;
+use crate::fsm::task1::Parallelism;
+use crate::fsm::MainStateMachine;
+use crate::machine::StateMachine;
+use crate::report::Format;
+use crate::tasks::subcommands;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, BufWriter, IsTerminal, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// the options of the usage are wrapped at this column
+const USAGE_WIDTH: usize = 92;
+
+// the usage after the options of the menu
+const USAGE_NOTES: &str = concat!(
+    "       course_project <command> [options] [--format <text|json>]\n",
+    "\n",
+    "Without a command the interactive menu is started. '--seed' fixes the random arrays and pivots,\n",
+    "'--record' saves the seed and every input line so the session can be replayed.\n",
+    "In a terminal the menu supports line editing, tab completion of commands and a history\n",
+    "kept in ~/.course_project_history or '--history'; '--no-editor' reads plain lines.\n",
+    "'--format json' writes one JSON record per operation instead of text and prompts.\n",
+    "\n",
+    "Commands:",
+);
+
+/// Usage of the program with the subcommands of every task.
+pub fn usage() -> String {
+    let mut lines = vec![
+        wrap_args("Usage: course_project", MENU_ARGS),
+        USAGE_NOTES.to_string(),
+    ];
+    lines.extend(
+        subcommands::<io::Sink>()
+            .iter()
+            .map(|command| command_usage(command.name, command.args)),
+    );
+    lines.extend([
+        command_usage("script", SCRIPT_ARGS),
+        "             Run the menu commands from a file, one per line, and write a transcript"
+            .to_string(),
+        command_usage("replay", REPLAY_ARGS),
+        "             Replay a session saved with '--record'".to_string(),
+        "  help       Print this message".to_string(),
+    ]);
+    lines.join("\n")
+}
+
+fn command_usage(name: &str, args: &[Arg]) -> String {
+    wrap_args(&format!("  {:<10}", name), args)
+}
+
+// `prefix` followed by the usage of `args`, the lines wrapped at `USAGE_WIDTH` are indented below
+// the first option
+fn wrap_args(prefix: &str, args: &[Arg]) -> String {
+    let indent = " ".repeat(prefix.len());
+    let mut usage = prefix.to_string();
+    let mut width = prefix.len();
+    for arg in args.iter().map(Arg::usage) {
+        if width > indent.len() && width + 1 + arg.len() > USAGE_WIDTH {
+            usage.push('\n');
+            usage.push_str(&indent);
+            width = indent.len();
+        }
+        usage.push(' ');
+        usage.push_str(&arg);
+        width += 1 + arg.len();
+    }
+    usage
+}
+
+/// An option of a command, accepted by its parser and shown in the usage.
+///
+/// Every command accepts `--format <text|json>` without listing it.
+pub enum Arg {
+    /// `--name <value>` that must be given.
+    Required(&'static str, &'static str),
+    /// `--name <value>` that may be omitted.
+    Optional(&'static str, &'static str),
+    /// `--name` without a value.
+    Flag(&'static str),
+    /// All options of either group, e.g. the bounds of a random array or the file it is read from.
+    Either(&'static [Arg], &'static [Arg]),
+}
+
+impl Arg {
+    fn usage(&self) -> String {
+        let group = |args: &[Arg]| {
+            args.iter()
+                .map(Arg::usage)
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        match self {
+            Arg::Required(name, value) => format!("--{} {}", name, value),
+            Arg::Optional(name, value) => format!("[--{} {}]", name, value),
+            Arg::Flag(name) => format!("[--{}]", name),
+            Arg::Either(first, second) => format!("({} | {})", group(first), group(second)),
+        }
+    }
+}
+
+// whether the option `name` of `args` takes a value, `None` if there is no such option
+fn takes_value(args: &[Arg], name: &str) -> Option<bool> {
+    args.iter().find_map(|arg| match arg {
+        Arg::Required(other, _) | Arg::Optional(other, _) => (*other == name).then_some(true),
+        Arg::Flag(other) => (*other == name).then_some(false),
+        Arg::Either(first, second) => {
+            takes_value(first, name).or_else(|| takes_value(second, name))
+        }
+    })
+}
+
+const MENU_ARGS: &[Arg] = &[
+    Arg::Optional("seed", "<n>"),
+    Arg::Optional("record", "<file>"),
+    Arg::Optional("format", "<text|json>"),
+    Arg::Optional("history", "<file>"),
+    Arg::Flag("no-editor"),
+];
+
+const SCRIPT_ARGS: &[Arg] = &[
+    Arg::Required("file", "<file>"),
+    Arg::Optional("transcript", "<file>"),
+    Arg::Flag("strict"),
+    Arg::Optional("seed", "<n>"),
+];
+
+const REPLAY_ARGS: &[Arg] = &[
+    Arg::Required("file", "<file>"),
+    Arg::Optional("transcript", "<file>"),
+    Arg::Flag("strict"),
+];
+
+/// The array of a command, generated or read from a file, see [`generate`].
+pub const ARRAY_ARGS: Arg = Arg::Either(
+    &[
+        Arg::Required("size", "<n>"),
+        Arg::Required("left", "<l>"),
+        Arg::Required("right", "<r>"),
+    ],
+    &[Arg::Required("input", "<file|->")],
+);
+
+#[derive(Debug)]
+pub enum CliError {
+    // invalid command line, usage is printed
+    Usage(String),
+    // the command was run and failed
+    Failed(String),
+    // the command failed and already reported the error in its output
+    Reported,
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(message) | CliError::Failed(message) => write!(f, "{}", message),
+            CliError::Reported => write!(f, "the error was reported in the output"),
+        }
+    }
+}
+
+/// Options of a command parsed with its [`Arg`] table.
+pub struct Options {
+    values: HashMap<String, String>,
+    flags: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: &[String], options: &[Arg]) -> Result<Self, CliError> {
+        let mut values = HashMap::new();
+        let mut parsed_flags = vec![];
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            let name = match arg.strip_prefix("--") {
+                Some(name) => name,
+                None => return Err(CliError::Usage(format!("Unexpected argument '{}'", arg))),
+            };
+            let takes_value = match name {
+                "format" => Some(true),
+                _ => takes_value(options, name),
+            };
+            match takes_value {
+                Some(false) => parsed_flags.push(name.to_string()),
+                Some(true) => match iter.next() {
+                    Some(value) => {
+                        values.insert(name.to_string(), value.to_string());
+                    }
+                    None => {
+                        return Err(CliError::Usage(format!(
+                            "Option '--{}' requires a value",
+                            name
+                        )))
+                    }
+                },
+                None => return Err(CliError::Usage(format!("Unknown option '--{}'", name))),
+            }
+        }
+
+        Ok(Options {
+            values,
+            flags: parsed_flags,
+        })
+    }
+
+    /// The value of the option `name`, a usage error if it is missing or invalid.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, CliError> {
+        match self.values.get(name) {
+            Some(value) => parse_value(name, value),
+            None => Err(CliError::Usage(format!("Option '--{}' is required", name))),
+        }
+    }
+
+    /// The value of the option `name`, `default` if it is missing.
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, CliError> {
+        match self.values.get(name) {
+            Some(value) => parse_value(name, value),
+            None => Ok(default),
+        }
+    }
+
+    /// The value of the option `name` if it is given.
+    pub fn get_optional<T: FromStr>(&self, name: &str) -> Result<Option<T>, CliError> {
+        self.values
+            .get(name)
+            .map(|value| parse_value(name, value))
+            .transpose()
+    }
+
+    /// The comma separated values of the option `name`, empty if it is missing.
+    pub fn get_list<T: FromStr>(&self, name: &str) -> Result<Vec<T>, CliError> {
+        match self.values.get(name) {
+            Some(value) => value
+                .split(',')
+                .map(|item| parse_value(name, item.trim()))
+                .collect(),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// The '--format' every command accepts.
+    pub fn format(&self) -> Result<Format, CliError> {
+        self.get_or("format", Format::Text)
+    }
+
+    /// Whether the flag `name` is given.
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, CliError> {
+    value
+        .parse::<T>()
+        .map_err(|_| CliError::Usage(format!("Invalid value '{}' for option '--{}'", value, name)))
+}
+
+pub fn run<W: Write>(out: &mut W, args: &[String]) -> Result<(), CliError> {
+    let (command, rest) = match args.split_first() {
+        Some((command, _)) if command.starts_with("--") && command != "--help" => {
+            return run_menu(out, args)
+        }
+        Some(split) => split,
+        None => return run_menu(out, args),
+    };
+    match command.as_str() {
+        "script" => run_script(out, rest),
+        "replay" => run_replay(out, rest),
+        "help" | "--help" | "-h" => {
+            outln!(out, "{}", usage());
+            Ok(())
+        }
+        _ => match subcommands().iter().find(|sub| sub.name == command) {
+            Some(subcommand) => (subcommand.run)(out, &Options::parse(rest, subcommand.args)?),
+            None => Err(CliError::Usage(format!("Unknown command '{}'", command))),
+        },
+    }
+}
+
+/// The array loaded from '--input' or generated from the size and bounds.
+pub fn generate(options: &Options) -> Result<Vec<i32>, CliError> {
+    if let Some(path) = options.get_optional::<String>("input")? {
+        return dataset::load(&path)
+            .map_err(|err| CliError::Failed(format!("Cannot load {}: {}", path, err)));
+    }
+    random_array(
+        options,
+        options.get::<usize>("size")?,
+        options.get::<i32>("left")?,
+        options.get::<i32>("right")?,
+    )
+}
+
+/// A random array of `size` values in `[left, right)` from the '--distribution' option.
+pub fn random_array(
+    options: &Options,
+    size: usize,
+    left: i32,
+    right: i32,
+) -> Result<Vec<i32>, CliError> {
+    if right <= left {
+        return Err(CliError::Usage(
+            "Right must be greater than left".to_string(),
+        ));
+    }
+    let distribution = options.get_or::<Distribution>("distribution", Distribution::Uniform)?;
+    let mut rng = StdRng::seed_from_u64(seed(options)?);
+    Ok(distribution.generate(&mut rng, size, left, right))
+}
+
+/// The '--seed' option, a random one if not given.
+pub fn seed(options: &Options) -> Result<u64, CliError> {
+    options.get_or::<u64>("seed", rand::random())
+}
+
+/// The '--threads' and '--cutoff' options of the parallel sorts.
+pub fn parallelism(options: &Options) -> Result<Parallelism, CliError> {
+    let default = Parallelism::default();
+    let threads = options.get_or::<usize>("threads", default.threads)?;
+    if threads == 0 {
+        return Err(CliError::Usage("Threads must be positive".to_string()));
+    }
+    Ok(Parallelism {
+        threads,
+        cutoff: options.get_or::<usize>("cutoff", default.cutoff)?,
+    })
+}
+
+fn run_menu(out: &mut impl Write, args: &[String]) -> Result<(), CliError> {
+    let options = Options::parse(args, MENU_ARGS)?;
+    let stdin = io::stdin();
+    if options.has_flag("no-editor") || !stdin.is_terminal() {
+        return run_menu_session(out, &options, stdin.lock());
+    }
+
+    let history = match options.get_optional::<String>("history")? {
+        Some(path) => Some(PathBuf::from(path)),
+        None => LineEditor::default_history(),
+    };
+    let editor = LineEditor::new(history)
+        .map_err(|err| CliError::Failed(format!("Cannot start line editor: {}", err)))?;
+    run_menu_session(out, &options, editor)
+}
+
+fn run_menu_session(
+    out: &mut impl Write,
+    options: &Options,
+    input: impl LineReader,
+) -> Result<(), CliError> {
+    let mut io = Io::new(input, out).with_format(options.format()?);
+    if let Some(seed) = options.get_optional::<u64>("seed")? {
+        io = io.with_seed(seed);
+    }
+    if let Some(path) = options.get_optional::<String>("record")? {
+        io = File::create(&path)
+            .and_then(|file| io.with_recorder(Box::new(file)))
+            .map_err(|err| CliError::Failed(format!("Cannot record to {}: {}", path, err)))?;
+    }
+    MainStateMachine::new(&mut io).start();
+    Ok(())
+}
+
+fn run_script(out: &mut impl Write, args: &[String]) -> Result<(), CliError> {
+    let options = Options::parse(args, SCRIPT_ARGS)?;
+    let path = options.get::<String>("file")?;
+    let script = open(&path)?;
+    let seed = options.get_optional::<u64>("seed")?;
+    run_file_session(out, &options, &path, script, seed)
+}
+
+fn run_replay(out: &mut impl Write, args: &[String]) -> Result<(), CliError> {
+    let options = Options::parse(args, REPLAY_ARGS)?;
+    let path = options.get::<String>("file")?;
+    let mut recording = open(&path)?;
+
+    let mut header = String::new();
+    BufRead::read_line(&mut recording, &mut header)
+        .map_err(|err| CliError::Failed(format!("Cannot read {}: {}", path, err)))?;
+    let seed = header
+        .trim()
+        .strip_prefix(RECORDING_HEADER)
+        .and_then(|seed| seed.parse::<u64>().ok())
+        .ok_or_else(|| CliError::Failed(format!("{} is not a session recording", path)))?;
+    run_file_session(out, &options, &path, recording, Some(seed))
+}
+
+fn open(path: &str) -> Result<BufReader<File>, CliError> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|err| CliError::Failed(format!("Cannot open {}: {}", path, err)))
+}
+
+fn run_file_session(
+    out: &mut impl Write,
+    options: &Options,
+    path: &str,
+    input: impl BufRead,
+    seed: Option<u64>,
+) -> Result<(), CliError> {
+    let strict = options.has_flag("strict");
+    let format = options.format()?;
+    let errors = match options.get_optional::<String>("transcript")? {
+        Some(transcript) => {
+            let file = File::create(&transcript).map_err(|err| {
+                CliError::Failed(format!("Cannot create {}: {}", transcript, err))
+            })?;
+            run_session(input, BufWriter::new(file), strict, format, seed)
+        }
+        None => run_session(input, out, strict, format, seed),
+    };
+
+    if strict && !errors.is_empty() {
+        return Err(CliError::Failed(format!(
+            "Script {} failed at {}",
+            path, errors[0]
+        )));
+    }
+    if !errors.is_empty() {
+        eprintln!(
+            "Script {} finished with {} input errors:",
+            path,
+            errors.len()
+        );
+        errors.iter().for_each(|error| eprintln!("  {}", error));
+    }
+    Ok(())
+}
+
+fn run_session(
+    input: impl BufRead,
+    output: impl Write,
+    strict: bool,
+    format: Format,
+    seed: Option<u64>,
+) -> Vec<String> {
+    let mut io = Io::new(input, output)
+        .with_echo(true)
+        .with_strict(strict)
+        .with_format(format);
+    if let Some(seed) = seed {
+        io = io.with_seed(seed);
+    }
+    MainStateMachine::new(&mut io).start();
+    io.errors().to_vec()
+}
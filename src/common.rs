@@ -1,11 +1,190 @@
-use rand::Rng;
-use std::io::Stdin;
+use crate::distribution::Distribution;
+use crate::machine::{commands, StateMachine};
+use crate::report::{Format, Record};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fmt;
+use std::io;
+use std::io::{BufRead, Write};
+
+/// Like `println!`, but writes to the given [`Write`] implementation.
+macro_rules! outln {
+    ($out:expr) => {
+        writeln!($out).expect("Error writing output")
+    };
+    ($out:expr, $($arg:tt)*) => {
+        writeln!($out, $($arg)*).expect("Error writing output")
+    };
+}
+pub(crate) use outln;
+
+/// Like [`outln!`], but also records the message as an input error of the session.
+macro_rules! errln {
+    ($io:expr, $($arg:tt)*) => {
+        $io.report_error(format_args!($($arg)*))
+    };
+}
+pub(crate) use errln;
+
+/// Source of input lines for [`Io`].
+///
+/// Implemented for every [`BufRead`] and for the interactive line editor.
+pub trait LineReader {
+    /// Appends the next line including the line break to `buf`, returns 0 at the end of input.
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize>;
+
+    /// Words offered for completion of the next line.
+    fn set_completions(&mut self, _completions: Vec<&'static str>) {}
+}
+
+impl<T: BufRead> LineReader for T {
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        BufRead::read_line(self, buf)
+    }
+}
+
+/// First line of a session recording, followed by the seed.
+pub const RECORDING_HEADER: &str = "seed ";
+
+/// Input, output and random number generator shared by all state machines of a session.
+pub struct Io<R: LineReader, W: Write> {
+    input: R,
+    output: W,
+    seed: u64,
+    rng: StdRng,
+    // receives every input line, see `with_recorder`
+    recorder: Option<Box<dyn Write>>,
+    // write every input line to the output, producing a transcript
+    echo: bool,
+    // stop the session on the first input error
+    strict: bool,
+    // prompts and echo are only written in text format
+    format: Format,
+    line: usize,
+    errors: Vec<String>,
+}
+
+impl<R: LineReader, W: Write> Io<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        let seed = rand::random();
+        Io {
+            input,
+            output,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            recorder: None,
+            echo: false,
+            strict: false,
+            format: Format::Text,
+            line: 0,
+            errors: vec![],
+        }
+    }
+
+    pub fn with_echo(mut self, echo: bool) -> Self {
+        self.echo = echo;
+        self
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Records the seed and every input line of the session to `recorder`, so the session can be
+    /// replayed later with the same random arrays.
+    pub fn with_recorder(mut self, mut recorder: Box<dyn Write>) -> io::Result<Self> {
+        writeln!(recorder, "{}{}", RECORDING_HEADER, self.seed)?;
+        recorder.flush()?;
+        self.recorder = Some(recorder);
+        Ok(self)
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    pub fn set_completions(&mut self, completions: Vec<&'static str>) {
+        self.input.set_completions(completions);
+    }
+
+    pub fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        let read = self.input.read_line(buf)?;
+        if read > 0 {
+            self.line += 1;
+            if let Some(recorder) = self.recorder.as_mut() {
+                let recorded = write!(recorder, "{}", buf).and_then(|_| recorder.flush());
+                if let Err(err) = recorded {
+                    outln!(self.output, "Error recording input: {}", err);
+                    self.recorder = None;
+                }
+            }
+            if self.echo && self.format == Format::Text {
+                outln!(self.output, "> {}", buf.trim_end_matches(['\r', '\n']));
+            }
+        }
+        Ok(read)
+    }
+
+    pub fn report_error(&mut self, message: fmt::Arguments) {
+        match self.format {
+            Format::Text => outln!(self.output, "{}", message),
+            Format::Json => outln!(
+                self.output,
+                "{}",
+                Record::new("session", "error")
+                    .input("line", self.line)
+                    .result("message", message.to_string())
+            ),
+        }
+        self.errors.push(format!("line {}: {}", self.line, message));
+    }
+
+    /// Input errors reported so far, prefixed with the number of the offending input line.
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Whether the session must be stopped because of an input error in strict mode.
+    pub fn is_failed(&self) -> bool {
+        self.strict && !self.errors.is_empty()
+    }
+}
+
+impl<R: LineReader, W: Write> Write for Io<R, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
 
 pub fn find_average(slice: &[f64]) -> f64 {
     if slice.is_empty() {
         return 0.0;
     }
-    return slice.iter().sum::<f64>() / (slice.len() as f64);
+    slice.iter().sum::<f64>() / (slice.len() as f64)
 }
 
 #[derive(Debug)]
@@ -24,78 +203,111 @@ pub enum Res<T> {
     Result(T),
 }
 
+impl<T> Res<T> {
+    /// Converts the result of a nested state machine into a result of its parent.
+    ///
+    /// `Back` returns control to the parent, `MainMenu` and `Exit` are passed up the chain.
+    pub fn nested<U>(self, on_result: impl FnOnce(T) -> Res<U>) -> Res<U> {
+        match self {
+            Res::Result(value) => on_result(value),
+            Res::Back | Res::Repeat => Res::Repeat,
+            Res::MainMenu => Res::MainMenu,
+            Res::Exit => Res::Exit,
+        }
+    }
+}
+
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 enum ArrayManualInputState {
     AwaitingInputSize,
     AwaitingInputLeft,
     AwaitingInputRight,
+    AwaitingDistribution,
 }
 
-pub struct ArrayGenerationInputStateMachine<'a> {
+pub struct ArrayGenerationInputStateMachine<'a, R: LineReader, W: Write> {
     state: ArrayManualInputState,
-    stdin: &'a Stdin,
-    rng: rand::rngs::ThreadRng,
+    io: &'a mut Io<R, W>,
     size: usize,
     left: i32,
     right: i32,
+    // ask for the distribution after the bounds, uniform otherwise
+    choose_distribution: bool,
 }
 
-impl<'a> ArrayGenerationInputStateMachine<'a> {
-    pub fn new(stdin: &'a Stdin, rng: rand::rngs::ThreadRng) -> Self {
+impl<'a, R: LineReader, W: Write> ArrayGenerationInputStateMachine<'a, R, W> {
+    pub fn new(io: &'a mut Io<R, W>) -> Self {
         ArrayGenerationInputStateMachine {
             state: ArrayManualInputState::AwaitingInputSize,
-            stdin,
-            rng,
+            io,
             size: 0,
             left: 0,
             right: 0,
+            choose_distribution: false,
         }
     }
 
-    fn print(&self) {
-        match self.state {
-            ArrayManualInputState::AwaitingInputSize => println!(
-                "Input array size or 'main' to return to main menu, or 'exit' to exit program:"
-            ),
-            ArrayManualInputState::AwaitingInputLeft => println!("Input left bound or 'back' to input size again or 'main' to return to main menu, or 'exit' to exit program:"),
-            ArrayManualInputState::AwaitingInputRight => println!("Input right bound or 'back' to input left bound again or 'main' to return to main menu, or 'exit' to exit program:"),
-        }
+    /// Asks for the [`Distribution`] of the values after the bounds.
+    pub fn with_distributions(mut self) -> Self {
+        self.choose_distribution = true;
+        self
     }
 
-    pub fn start(&mut self) -> Res<Vec<i32>> {
-        let mut buffer = String::new();
+    fn gen(&mut self, distribution: Distribution) -> Vec<i32> {
+        distribution.generate(self.io.rng(), self.size, self.left, self.right)
+    }
+}
 
-        loop {
-            self.print();
-            if self.stdin.read_line(&mut buffer).is_ok() {
-                match self.handle_input(&buffer) {
-                    Res::Repeat => (),
-                    res => return res,
-                }
-            } else {
-                println!("Error reading input");
+commands! {
+    pub enum ArrayInputCommands {}
+}
+
+impl<'a, R: LineReader, W: Write> StateMachine<R, W>
+    for ArrayGenerationInputStateMachine<'a, R, W>
+{
+    type Command = ArrayInputCommands;
+    type Output = Vec<i32>;
+
+    fn io(&mut self) -> &mut Io<R, W> {
+        self.io
+    }
+
+    fn commands(&self) -> Vec<ArrayInputCommands> {
+        vec![]
+    }
+
+    fn back(&mut self) -> Res<Vec<i32>> {
+        match self.state {
+            ArrayManualInputState::AwaitingInputSize => return Res::Back,
+            ArrayManualInputState::AwaitingInputLeft => {
+                self.state = ArrayManualInputState::AwaitingInputSize;
+            }
+            ArrayManualInputState::AwaitingInputRight => {
+                self.state = ArrayManualInputState::AwaitingInputLeft;
+            }
+            ArrayManualInputState::AwaitingDistribution => {
+                self.state = ArrayManualInputState::AwaitingInputRight;
             }
-            buffer.clear();
         }
+        Res::Repeat
     }
 
-    fn handle_input(&mut self, input: &str) -> Res<Vec<i32>> {
-        match input.trim() {
-            "main" => return Res::MainMenu,
-            "exit" => return Res::Exit,
-            "back" => match self.state {
-                ArrayManualInputState::AwaitingInputSize => return Res::Back,
-                ArrayManualInputState::AwaitingInputLeft => {
-                    self.state = ArrayManualInputState::AwaitingInputSize;
-                    return Res::Repeat;
-                }
-                ArrayManualInputState::AwaitingInputRight => {
-                    self.state = ArrayManualInputState::AwaitingInputLeft;
-                    return Res::Repeat;
-                }
-            },
-            _ => (),
+    fn print(&mut self) {
+        match self.state {
+            ArrayManualInputState::AwaitingInputSize => outln!(self.io,
+                "Input array size or 'back' to cancel or 'main' to return to main menu, or 'exit' to exit program:"
+            ),
+            ArrayManualInputState::AwaitingInputLeft => outln!(self.io, "Input left bound or 'back' to input size again or 'main' to return to main menu, or 'exit' to exit program:"),
+            ArrayManualInputState::AwaitingInputRight => outln!(self.io, "Input right bound or 'back' to input left bound again or 'main' to return to main menu, or 'exit' to exit program:"),
+            ArrayManualInputState::AwaitingDistribution => outln!(self.io,
+                "Input distribution ({}), empty for uniform, or 'back' to input right bound again or 'main' to return to main menu, or 'exit' to exit program:",
+                Distribution::usage().join(", ")
+            ),
         }
+    }
+
+    fn handle_input(&mut self, input: &str, _command: Option<ArrayInputCommands>) -> Res<Vec<i32>> {
         match self.state {
             ArrayManualInputState::AwaitingInputSize => match input.trim().parse::<usize>() {
                 Ok(size) => {
@@ -104,7 +316,8 @@ impl<'a> ArrayGenerationInputStateMachine<'a> {
                     Res::Repeat
                 }
                 Err(_) => {
-                    println!(
+                    errln!(
+                        self.io,
                         "Cannot parse input. Size must be a number between {} and {}",
                         usize::MIN,
                         usize::MAX,
@@ -119,7 +332,8 @@ impl<'a> ArrayGenerationInputStateMachine<'a> {
                     Res::Repeat
                 }
                 Err(_) => {
-                    println!(
+                    errln!(
+                        self.io,
                         "Cannot parse input. Left must be a number between {} and {}",
                         i32::MIN,
                         i32::MAX
@@ -129,15 +343,20 @@ impl<'a> ArrayGenerationInputStateMachine<'a> {
             },
             ArrayManualInputState::AwaitingInputRight => match input.trim().parse::<i32>() {
                 Ok(right) => {
-                    if right < self.left {
-                        println!("Right must be greater than left");
+                    if right <= self.left {
+                        errln!(self.io, "Right must be greater than left");
                         return Res::Repeat;
                     };
                     self.right = right;
-                    Res::Result(self.gen())
+                    if self.choose_distribution {
+                        self.state = ArrayManualInputState::AwaitingDistribution;
+                        return Res::Repeat;
+                    }
+                    Res::Result(self.gen(Distribution::Uniform))
                 }
                 Err(_) => {
-                    println!(
+                    errln!(
+                        self.io,
                         "Cannot parse input. Right must be a number between {} and {}",
                         i32::MIN,
                         i32::MAX
@@ -145,11 +364,23 @@ impl<'a> ArrayGenerationInputStateMachine<'a> {
                     Res::Repeat
                 }
             },
+            ArrayManualInputState::AwaitingDistribution => {
+                let input = input.trim();
+                if input.is_empty() {
+                    return Res::Result(self.gen(Distribution::Uniform));
+                }
+                match input.parse::<Distribution>() {
+                    Ok(distribution) => Res::Result(self.gen(distribution)),
+                    Err(_) => {
+                        errln!(
+                            self.io,
+                            "Unknown distribution. Available: {}",
+                            Distribution::usage().join(", ")
+                        );
+                        Res::Repeat
+                    }
+                }
+            }
         }
     }
-    fn gen(&mut self) -> Vec<i32> {
-        (0..self.size)
-            .map(|_| self.rng.gen_range(self.left..self.right))
-            .collect()
-    }
 }
@@ -0,0 +1,189 @@
+use crate::common::outln;
+use crate::report::{emit, Format, Record};
+use course_project::task1::{read_values, write_values, FileFormat};
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Path standing for the standard input.
+pub const STDIN: &str = "-";
+
+/// Reads an array from the file at `path`, or from the standard input if the path is [`STDIN`].
+///
+/// Files ending with `.bin` hold little-endian `i32` values, other files and the standard input
+/// integers separated by whitespace, commas or line breaks.
+pub fn load(path: &str) -> io::Result<Vec<i32>> {
+    match path {
+        STDIN => read_values(io::stdin().lock(), FileFormat::Text),
+        _ => read_values(
+            BufReader::new(File::open(path)?),
+            FileFormat::of_path(Path::new(path)),
+        ),
+    }
+}
+
+/// Writes the array to the file at `path` in the format [`load`] reads from it, one value per line
+/// for text files.
+pub fn save(path: &str, vec: &[i32]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    write_values(&mut file, FileFormat::of_path(Path::new(path)), vec)
+}
+
+/// Loads an array for the menu of `task` and reports its size or the error.
+pub fn report_load(
+    out: &mut impl Write,
+    format: Format,
+    task: &'static str,
+    path: &str,
+) -> Option<Vec<i32>> {
+    let loaded = load(path);
+    let record = Record::new(task, "load").input("path", path);
+    let record = match &loaded {
+        Ok(vec) => record.result("loaded", true).result("size", vec.len()),
+        Err(err) => record
+            .result("loaded", false)
+            .result("error", err.to_string()),
+    };
+    emit(out, format, record, |out| match &loaded {
+        Ok(vec) => outln!(out, "Loaded {} values from {}", vec.len(), path),
+        Err(err) => outln!(out, "Cannot load {}: {}", path, err),
+    });
+    loaded.ok()
+}
+
+/// Saves the array of the menu of `task` and reports the result.
+pub fn report_save(
+    out: &mut impl Write,
+    format: Format,
+    task: &'static str,
+    path: &str,
+    vec: &[i32],
+) {
+    let saved = save(path, vec);
+    let record = Record::new(task, "save")
+        .input("path", path)
+        .input("size", vec.len());
+    let record = match &saved {
+        Ok(()) => record.result("saved", true),
+        Err(err) => record
+            .result("saved", false)
+            .result("error", err.to_string()),
+    };
+    emit(out, format, record, |out| match saved {
+        Ok(()) => outln!(out, "Saved {} values to {}", vec.len(), path),
+        Err(err) => outln!(out, "Cannot save to {}: {}", path, err),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process;
+
+    // directory of the files of one test, removed with everything in it when dropped
+    struct TestDir {
+        path: PathBuf,
+    }
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("course_project_{}_{}", name, process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            TestDir { path }
+        }
+
+        fn file(&self, name: &str) -> String {
+            self.path.join(name).to_str().unwrap().to_string()
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    #[test]
+    fn loads_what_it_saves() {
+        let dir = TestDir::new("dataset_round_trip");
+        let vec = vec![3, -1, i32::MAX, 0, i32::MIN, 3];
+        for name in ["array.txt", "array.csv", "array", "array.bin"] {
+            let path = dir.file(name);
+            save(&path, &vec).unwrap();
+            assert_eq!(load(&path).unwrap(), vec, "{}", name);
+        }
+        assert_eq!(
+            fs::read_to_string(dir.file("array.txt"))
+                .unwrap()
+                .lines()
+                .count(),
+            6
+        );
+        assert_eq!(fs::metadata(dir.file("array.bin")).unwrap().len(), 24);
+
+        save(&dir.file("empty.txt"), &[]).unwrap();
+        assert_eq!(load(&dir.file("empty.txt")).unwrap(), []);
+    }
+
+    #[test]
+    fn loads_separated_text() {
+        let dir = TestDir::new("dataset_text");
+        let path = dir.file("array.txt");
+        fs::write(&path, " 1,2\t3\r\n\n-4 ,, 5\n").unwrap();
+        assert_eq!(load(&path).unwrap(), [1, 2, 3, -4, 5]);
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        let dir = TestDir::new("dataset_malformed");
+        for (name, content) in [
+            ("word.txt", "1 two 3".as_bytes()),
+            ("overflow.txt", b"2147483648"),
+            ("float.txt", b"1.5"),
+            ("truncated.bin", &[1, 0, 0, 0, 2, 0]),
+        ] {
+            let path = dir.file(name);
+            fs::write(&path, content).unwrap();
+            let err = load(&path).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", name);
+        }
+        let err = load(&dir.file("missing.txt")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(save(&dir.file("missing/array.txt"), &[1]).is_err());
+    }
+
+    #[test]
+    fn reports_load_and_save_errors() {
+        let dir = TestDir::new("dataset_reports");
+        let path = dir.file("word.txt");
+        fs::write(&path, "1 x").unwrap();
+        let mut out = vec![];
+        assert_eq!(report_load(&mut out, Format::Text, "sort", &path), None);
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, format!("Cannot load {}: invalid integer 'x'\n", path));
+
+        let mut out = vec![];
+        let missing = dir.file("missing/array.txt");
+        report_save(&mut out, Format::Json, "sort", &missing, &[1, 2]);
+        let json = String::from_utf8(out).unwrap();
+        assert!(json.starts_with(r#"{"task":"sort","operation":"save","#));
+        assert!(json.contains(r#""result":{"saved":false,"error":"#));
+
+        let saved = dir.file("array.bin");
+        let mut out = vec![];
+        report_save(&mut out, Format::Text, "sort", &saved, &[1, 2]);
+        let mut out = vec![];
+        assert_eq!(
+            report_load(&mut out, Format::Json, "sort", &saved),
+            Some(vec![1, 2])
+        );
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains(r#""result":{"loaded":true,"size":2}"#));
+    }
+}
@@ -0,0 +1,326 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_distr::{Normal, Zipf};
+use std::fmt;
+use std::str::FromStr;
+
+const DEFAULT_SWAPS: usize = 10;
+const DEFAULT_TEETH: usize = 4;
+const DEFAULT_UNIQUE: usize = 8;
+const ZIPF_EXPONENT: f64 = 1.0;
+
+/// Shape of a generated array. Every value lies in `left..right`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distribution {
+    /// Uniformly random values.
+    Uniform,
+    /// Uniformly random values in ascending order.
+    Sorted,
+    /// Uniformly random values in descending order.
+    Reversed,
+    /// Sorted values with the given number of random pairs swapped, at most one swap per value.
+    NearlySorted(usize),
+    /// Ascending then descending sorted values.
+    OrganPipe,
+    /// The given number of ascending runs of random values, at most one run per value.
+    Sawtooth(usize),
+    /// Values drawn from the given number of random values, at most one per value of the array.
+    FewUnique(usize),
+    /// One random value repeated.
+    AllEqual,
+    /// Normally distributed around the middle of the range, clamped to the range.
+    Gaussian,
+    /// Zipf distributed: `left` is the most frequent value, `left + 1` the next and so on.
+    Zipf,
+}
+
+impl Distribution {
+    /// Every distribution with the default parameter, in the order they are listed to the user.
+    pub const ALL: [Distribution; 10] = [
+        Distribution::Uniform,
+        Distribution::Sorted,
+        Distribution::Reversed,
+        Distribution::NearlySorted(DEFAULT_SWAPS),
+        Distribution::OrganPipe,
+        Distribution::Sawtooth(DEFAULT_TEETH),
+        Distribution::FewUnique(DEFAULT_UNIQUE),
+        Distribution::AllEqual,
+        Distribution::Gaussian,
+        Distribution::Zipf,
+    ];
+
+    /// Name of the distribution without its parameter.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distribution::Uniform => "uniform",
+            Distribution::Sorted => "sorted",
+            Distribution::Reversed => "reversed",
+            Distribution::NearlySorted(_) => "nearly-sorted",
+            Distribution::OrganPipe => "organ-pipe",
+            Distribution::Sawtooth(_) => "sawtooth",
+            Distribution::FewUnique(_) => "few-unique",
+            Distribution::AllEqual => "all-equal",
+            Distribution::Gaussian => "gaussian",
+            Distribution::Zipf => "zipf",
+        }
+    }
+
+    fn parameter(&self) -> Option<usize> {
+        match self {
+            Distribution::NearlySorted(n)
+            | Distribution::Sawtooth(n)
+            | Distribution::FewUnique(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Names of all distributions as accepted by [`str::parse`], parameters in angle brackets.
+    pub fn usage() -> Vec<String> {
+        Distribution::ALL
+            .iter()
+            .map(|distribution| match distribution {
+                Distribution::NearlySorted(_) => "nearly-sorted[:<swaps>]".to_string(),
+                Distribution::Sawtooth(_) => "sawtooth[:<runs>]".to_string(),
+                Distribution::FewUnique(_) => "few-unique[:<values>]".to_string(),
+                distribution => distribution.name().to_string(),
+            })
+            .collect()
+    }
+
+    /// Generates `size` values in `left..right`, `left` must be less than `right`.
+    pub fn generate(&self, rng: &mut impl Rng, size: usize, left: i32, right: i32) -> Vec<i32> {
+        let uniform = |rng: &mut _| generate_array(rng, size, left, right);
+        match *self {
+            Distribution::Uniform => uniform(rng),
+            Distribution::Sorted => {
+                let mut vec = uniform(rng);
+                vec.sort_unstable();
+                vec
+            }
+            Distribution::Reversed => {
+                let mut vec = uniform(rng);
+                vec.sort_unstable_by(|a, b| b.cmp(a));
+                vec
+            }
+            Distribution::NearlySorted(swaps) => {
+                let mut vec = uniform(rng);
+                vec.sort_unstable();
+                if size > 1 {
+                    for _ in 0..swaps.min(size) {
+                        vec.swap(rng.gen_range(0..size), rng.gen_range(0..size));
+                    }
+                }
+                vec
+            }
+            Distribution::OrganPipe => {
+                let mut sorted = uniform(rng);
+                sorted.sort_unstable();
+                // every second value goes up, the rest comes down
+                let up = sorted.iter().step_by(2);
+                let down = sorted.iter().skip(1).step_by(2).rev();
+                up.chain(down).copied().collect()
+            }
+            Distribution::Sawtooth(runs) => {
+                let mut vec = uniform(rng);
+                let run_len = size.div_ceil(runs.max(1)).max(1);
+                vec.chunks_mut(run_len).for_each(|run| run.sort_unstable());
+                vec
+            }
+            Distribution::FewUnique(count) => {
+                let values = generate_array(rng, count.clamp(1, size.max(1)), left, right);
+                (0..size)
+                    .map(|_| *values.choose(rng).expect("values are not empty"))
+                    .collect()
+            }
+            Distribution::AllEqual => vec![rng.gen_range(left..right); size],
+            Distribution::Gaussian => {
+                let (low, high) = (left as f64, right as f64 - 1.0);
+                let normal = Normal::new((low + high) / 2.0, (high - low + 1.0) / 6.0)
+                    .expect("standard deviation is positive");
+                (0..size)
+                    .map(|_| rng.sample(normal).round().clamp(low, high) as i32)
+                    .collect()
+            }
+            Distribution::Zipf => {
+                let values = (right as i64 - left as i64) as u64;
+                let zipf = Zipf::new(values, ZIPF_EXPONENT).expect("range is not empty");
+                (0..size)
+                    .map(|_| (left as i64 + rng.sample(zipf) as i64 - 1) as i32)
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Uniformly random values in `left..right`.
+pub fn generate_array(rng: &mut impl Rng, size: usize, left: i32, right: i32) -> Vec<i32> {
+    (0..size).map(|_| rng.gen_range(left..right)).collect()
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.parameter() {
+            Some(parameter) => write!(f, "{}:{}", self.name(), parameter),
+            None => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown distribution or an invalid parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownDistributionError;
+
+impl fmt::Display for UnknownDistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown distribution")
+    }
+}
+
+impl std::error::Error for UnknownDistributionError {}
+
+impl FromStr for Distribution {
+    type Err = UnknownDistributionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, parameter) = match value.split_once(':') {
+            Some((name, parameter)) => (
+                name,
+                Some(
+                    parameter
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|_| UnknownDistributionError)?,
+                ),
+            ),
+            None => (value, None),
+        };
+        let distribution = Distribution::ALL
+            .iter()
+            .copied()
+            .find(|distribution| distribution.name() == name.trim())
+            .ok_or(UnknownDistributionError)?;
+        match (distribution, parameter) {
+            (distribution, None) => Ok(distribution),
+            (Distribution::NearlySorted(_), Some(swaps)) => Ok(Distribution::NearlySorted(swaps)),
+            (Distribution::Sawtooth(_), Some(runs)) => Ok(Distribution::Sawtooth(runs)),
+            (Distribution::FewUnique(_), Some(count)) => Ok(Distribution::FewUnique(count)),
+            (_, Some(_)) => Err(UnknownDistributionError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    const SIZE: usize = 1000;
+
+    fn generate(distribution: Distribution) -> Vec<i32> {
+        let vec = distribution.generate(&mut StdRng::seed_from_u64(1), SIZE, -500, 500);
+        assert_eq!(vec.len(), SIZE);
+        assert!(vec.iter().all(|value| (-500..500).contains(value)));
+        vec
+    }
+
+    fn distinct(vec: &[i32]) -> usize {
+        vec.iter().collect::<HashSet<_>>().len()
+    }
+
+    fn descents(vec: &[i32]) -> usize {
+        vec.windows(2).filter(|pair| pair[0] > pair[1]).count()
+    }
+
+    #[test]
+    fn parses_what_it_displays() {
+        let with_parameters = [
+            Distribution::NearlySorted(0),
+            Distribution::Sawtooth(1),
+            Distribution::FewUnique(usize::MAX),
+        ];
+        for distribution in Distribution::ALL.into_iter().chain(with_parameters) {
+            assert_eq!(distribution.to_string().parse(), Ok(distribution));
+        }
+        assert_eq!(
+            "few-unique".parse(),
+            Ok(Distribution::FewUnique(DEFAULT_UNIQUE))
+        );
+        assert_eq!(" sawtooth : 3 ".parse(), Ok(Distribution::Sawtooth(3)));
+        for invalid in [
+            "",
+            "normal",
+            "uniform:3",
+            "few-unique:",
+            "few-unique:-1",
+            "sawtooth:x",
+        ] {
+            assert_eq!(
+                invalid.parse::<Distribution>(),
+                Err(UnknownDistributionError)
+            );
+        }
+    }
+
+    #[test]
+    fn generates_the_shape_of_each_distribution() {
+        let uniform = generate(Distribution::Uniform);
+        assert!(distinct(&uniform) > SIZE / 2);
+        assert_eq!(descents(&generate(Distribution::Sorted)), 0);
+        let mut reversed = generate(Distribution::Reversed);
+        reversed.reverse();
+        assert_eq!(descents(&reversed), 0);
+
+        let nearly_sorted = generate(Distribution::NearlySorted(5));
+        let mut sorted = nearly_sorted.clone();
+        sorted.sort();
+        let moved = nearly_sorted.iter().zip(&sorted).filter(|(a, b)| a != b);
+        assert!(moved.count() <= 10);
+
+        let organ_pipe = generate(Distribution::OrganPipe);
+        let peak = organ_pipe
+            .iter()
+            .position(|value| value == organ_pipe.iter().max().unwrap());
+        let (up, down) = organ_pipe.split_at(peak.unwrap());
+        assert_eq!(descents(up), 0);
+        assert!(down.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        assert!(descents(&generate(Distribution::Sawtooth(4))) <= 3);
+        assert!(distinct(&generate(Distribution::FewUnique(8))) <= 8);
+        assert_eq!(distinct(&generate(Distribution::AllEqual)), 1);
+
+        let gaussian = generate(Distribution::Gaussian);
+        let mean = gaussian.iter().map(|&value| value as f64).sum::<f64>() / SIZE as f64;
+        assert!(mean.abs() < 20.0);
+
+        let zipf = generate(Distribution::Zipf);
+        let count = |value| zipf.iter().filter(|&&other| other == value).count();
+        assert!(count(-500) > count(-499));
+        assert!(count(-499) > SIZE / 20);
+    }
+
+    #[test]
+    fn bounds_parameters_by_the_size() {
+        let nearly_sorted = generate(Distribution::NearlySorted(usize::MAX));
+        assert_eq!(
+            distinct(&nearly_sorted),
+            distinct(&generate(Distribution::Sorted))
+        );
+        assert!(distinct(&generate(Distribution::FewUnique(usize::MAX))) <= SIZE);
+        assert!(descents(&generate(Distribution::Sawtooth(usize::MAX))) < SIZE);
+        let mut rng = StdRng::seed_from_u64(1);
+        for distribution in Distribution::ALL {
+            assert_eq!(distribution.generate(&mut rng, 0, 0, 1), []);
+            assert_eq!(distribution.generate(&mut rng, 1, 0, 1), [0]);
+        }
+        assert_eq!(
+            Distribution::FewUnique(0).generate(&mut rng, 3, 7, 8),
+            [7; 3]
+        );
+        assert_eq!(
+            Distribution::Sawtooth(0).generate(&mut rng, 3, 7, 8),
+            [7; 3]
+        );
+    }
+}
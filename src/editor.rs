@@ -0,0 +1,110 @@
+use crate::common::LineReader;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::io;
+use std::path::PathBuf;
+
+/// File in the home directory the command history is kept in between sessions.
+pub const HISTORY_FILE: &str = ".course_project_history";
+
+const PROMPT: &str = "> ";
+
+/// Completes the commands of the current menu.
+#[derive(Default)]
+struct CommandCompleter {
+    commands: Vec<&'static str>,
+}
+
+impl Completer for CommandCompleter {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let prefix = line[..pos].trim_start();
+        let candidates = self
+            .commands
+            .iter()
+            .filter(|command| command.starts_with(prefix))
+            .map(|command| command.to_string())
+            .collect();
+        Ok((pos - prefix.len(), candidates))
+    }
+}
+
+impl Hinter for CommandCompleter {
+    type Hint = String;
+}
+
+impl Highlighter for CommandCompleter {}
+
+impl Validator for CommandCompleter {}
+
+impl Helper for CommandCompleter {}
+
+/// Line editor for the interactive menu with history and tab completion of commands.
+///
+/// The history is loaded from `history` on creation and saved back when the editor is dropped.
+pub struct LineEditor {
+    editor: Editor<CommandCompleter, FileHistory>,
+    history: Option<PathBuf>,
+}
+
+impl LineEditor {
+    pub fn new(history: Option<PathBuf>) -> rustyline::Result<Self> {
+        let mut editor = Editor::new()?;
+        editor.set_helper(Some(CommandCompleter::default()));
+        if let Some(path) = &history {
+            // a missing history file is expected on the first run
+            let _ = editor.load_history(path);
+        }
+        Ok(LineEditor { editor, history })
+    }
+
+    /// History file in the home directory, if the home directory is known.
+    pub fn default_history() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE))
+    }
+}
+
+impl LineReader for LineEditor {
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        match self.editor.readline(PROMPT) {
+            Ok(line) => {
+                if !line.trim().is_empty() {
+                    let _ = self.editor.add_history_entry(line.as_str());
+                }
+                buf.push_str(&line);
+                buf.push('\n');
+                Ok(line.len() + 1)
+            }
+            Err(ReadlineError::Eof | ReadlineError::Interrupted) => Ok(0),
+            Err(ReadlineError::Io(err)) => Err(err),
+            Err(err) => Err(io::Error::other(err)),
+        }
+    }
+
+    fn set_completions(&mut self, completions: Vec<&'static str>) {
+        if let Some(helper) = self.editor.helper_mut() {
+            helper.commands = completions;
+        }
+    }
+}
+
+impl Drop for LineEditor {
+    fn drop(&mut self) {
+        if let Some(path) = &self.history {
+            if let Err(err) = self.editor.save_history(path) {
+                eprintln!("Cannot save history to {}: {}", path.display(), err);
+            }
+        }
+    }
+}
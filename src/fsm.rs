@@ -1,117 +1,176 @@
-use crate::common::{InvalidEnumValueError, Res};
-use crate::task1::fsm::SortStateMachine;
-use crate::task2::fsm::Task2StateMachine;
-use crate::task3::fsm::SimplifiedBoyerMoorStateMachine;
-use crate::task4::fsm::CheckParenthesesStateMachine;
-use crate::task5::fsm::KochCurveDrawerStateMachine;
-use std::io::Stdin;
+use crate::common::{errln, outln, Io, LineReader, Res};
+use crate::machine::{commands, GlobalCommands, StateMachine};
+use crate::tasks::{tasks, Task};
+use std::io::Write;
+
+pub mod task1;
+pub mod task2;
+pub mod task3;
+pub mod task4;
+pub mod task5;
 
 #[derive(Debug)]
 enum MainState {
     AwaitingTaskNumber,
 }
 
-#[derive(Debug)]
-enum MainCommands {
-    Exit,
+commands! {
+    pub enum MainCommands {}
+}
+
+pub struct MainStateMachine<'a, R: LineReader, W: Write> {
+    state: MainState,
+    io: &'a mut Io<R, W>,
+    tasks: Vec<Task<R, W>>,
 }
 
-impl MainCommands {
-    pub fn from_text(value: &str) -> Result<Self, InvalidEnumValueError> {
-        match value {
-            "exit" => Ok(MainCommands::Exit),
-            _ => Err(InvalidEnumValueError),
+impl<'a, R: LineReader, W: Write> MainStateMachine<'a, R, W> {
+    pub fn new(io: &'a mut Io<R, W>) -> Self {
+        MainStateMachine {
+            state: MainState::AwaitingTaskNumber,
+            io,
+            tasks: tasks(),
         }
     }
 
-    pub fn to_text(&self) -> &'static str {
-        match self {
-            MainCommands::Exit => "'exit' - Exit program",
+    fn parse_task_number(&mut self, input: &str) -> Res<()> {
+        let input = input.trim();
+        let task = match input.parse::<usize>() {
+            Ok(number) => number.checked_sub(1).and_then(|idx| self.tasks.get(idx)),
+            Err(_) => self.tasks.iter().find(|task| task.name == input),
+        };
+        let res = match task {
+            Some(task) => (task.start)(self.io),
+            None => {
+                errln!(
+                    self.io,
+                    "Invalid task number. Available tasks: 1-{}",
+                    self.tasks.len()
+                );
+                Res::Repeat
+            }
+        };
+        match res {
+            Res::Exit => Res::Exit,
+            _ => Res::Repeat,
         }
     }
 }
 
-pub struct MainStateMachine<'a> {
-    state: MainState,
-    stdin: &'a Stdin,
-}
+impl<'a, R: LineReader, W: Write> StateMachine<R, W> for MainStateMachine<'a, R, W> {
+    type Command = MainCommands;
+    type Output = ();
 
-impl<'a> MainStateMachine<'a> {
-    pub fn new(stdin: &'a Stdin) -> Self {
-        MainStateMachine {
-            state: MainState::AwaitingTaskNumber,
-            stdin,
-        }
+    fn io(&mut self) -> &mut Io<R, W> {
+        self.io
+    }
+
+    fn commands(&self) -> Vec<MainCommands> {
+        vec![]
     }
 
-    fn print(&self) {
+    fn global_commands(&self) -> &'static [GlobalCommands] {
+        &[GlobalCommands::Help, GlobalCommands::Exit]
+    }
+
+    fn print(&mut self) {
         match self.state {
             MainState::AwaitingTaskNumber => {
-                println!(
-                    "Available tasks:\n\
-                1 - Sort\n\
-                2 - Fibonacci search and simple rehash\n\
-                3 - Simplified Boyer-Moor search\n\
-                4 - Check parentheses balance\n\
-                5 - Draw Koch curve"
-                );
-                println!(
-                    "Enter task number (1-5) for running or\n{}",
-                    [MainCommands::Exit.to_text()].join("\n")
+                let tasks = self
+                    .tasks
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, task)| format!("{} - {}", idx + 1, task.description))
+                    .collect::<Vec<String>>()
+                    .join("\n");
+                outln!(self.io, "Available tasks:\n{}", tasks);
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter task number (1-{}) or name for running or\n{}",
+                    self.tasks.len(),
+                    commands
                 );
             }
         }
     }
 
-    pub fn start(&mut self) -> Res<()> {
-        let mut buffer = String::new();
-
-        loop {
-            self.print();
-            if self.stdin.read_line(&mut buffer).is_ok() {
-                match self.handle_input(&buffer) {
-                    Res::Repeat => (),
-                    Res::MainMenu => (),
-                    res => return res,
-                }
-            } else {
-                println!("Error reading input");
-            }
-            buffer.clear();
+    fn handle_input(&mut self, input: &str, _command: Option<MainCommands>) -> Res<()> {
+        match self.state {
+            MainState::AwaitingTaskNumber => self.parse_task_number(input),
         }
     }
+}
 
-    fn handle_input(&mut self, input: &str) -> Res<()> {
-        match (&self.state, self.parse_command(input)) {
-            (MainState::AwaitingTaskNumber, _) => self.parse_task_number(input),
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    // runs the main menu on the script like the 'script' command, returns the transcript and the
+    // input errors
+    fn run_script(script: &str, strict: bool) -> (String, Vec<String>) {
+        let mut output = vec![];
+        let mut io = Io::new(Cursor::new(script), &mut output)
+            .with_echo(true)
+            .with_strict(strict);
+        assert!(matches!(MainStateMachine::new(&mut io).start(), Res::Exit));
+        let errors = io.errors().to_vec();
+        drop(io);
+        (String::from_utf8(output).unwrap(), errors)
     }
 
-    fn parse_task_number(&self, input: &str) -> Res<()> {
-        match input.trim().parse::<usize>() {
-            Ok(number) => match number {
-                1 => SortStateMachine::new(self.stdin).start(),
-                2 => Task2StateMachine::new(self.stdin).start(),
-                3 => SimplifiedBoyerMoorStateMachine::new(self.stdin).start(),
-                4 => CheckParenthesesStateMachine::new(self.stdin).start(),
-                5 => KochCurveDrawerStateMachine::new(self.stdin).start(),
-                _ => {
-                    println!("Invalid task number. Available tasks: 1-5");
-                    Res::Repeat
-                }
-            },
-            Err(_) => {
-                println!("Invalid task number. Available tasks: 1-5");
+    #[test]
+    fn replays_a_script() {
+        let script = "parentheses\nstring\n(()\nback\n3\nstring\nabcabc\nbc\nsearch\nmain\nexit\n";
+        let (transcript, errors) = run_script(script, true);
+        assert!(errors.is_empty());
+        let echoed = transcript
+            .lines()
+            .filter_map(|line| line.strip_prefix("> "))
+            .collect::<Vec<&str>>();
+        assert_eq!(echoed, script.lines().collect::<Vec<&str>>());
+        assert!(transcript.contains("> (()\nParentheses are not balanced, extra 1 '(' parentheses"));
+        assert!(transcript.contains("> search\nSBM: Pattern found at positions: [1, 4]."));
+        assert!(transcript.ends_with("> exit\n"));
+    }
 
-                Res::Repeat
-            }
-        }
+    #[test]
+    fn end_of_script_exits() {
+        let (transcript, errors) = run_script("4\nstring\n()\n", false);
+        assert!(errors.is_empty());
+        assert!(transcript.contains("> ()\nParentheses are balanced\n"));
     }
 
-    fn parse_command(&self, input: &str) -> Option<MainCommands> {
-        match MainCommands::from_text(input.trim()) {
-            Ok(command) => Some(command),
-            Err(_) => None,
-        }
+    #[test]
+    fn free_text_is_not_a_command() {
+        let script = concat!(
+            "4\nstring\nback\nstring\nexit\nback\n",
+            "3\nstring\nhelp\nstring\nsearch\nstring\nsearch\nsearch\nsearch\nmain\n",
+            "exit\n"
+        );
+        let (transcript, errors) = run_script(script, true);
+        assert!(errors.is_empty());
+        assert!(transcript.contains("> back\nParentheses are balanced\n"));
+        assert!(transcript.contains("> exit\nParentheses are balanced\n"));
+        assert!(!transcript.contains("'help' - Print commands available now\n> help"));
+        assert!(transcript.contains("String: help\nPattern: string\n"));
+        assert!(transcript.contains("String: search\nPattern: search\n"));
+        assert!(transcript.contains("> search\nSBM: Pattern found at positions: [0]."));
+        assert!(transcript.ends_with("> exit\n"));
+    }
+
+    #[test]
+    fn strict_script_stops_at_the_first_error() {
+        let (transcript, errors) = run_script("9\n4\nexit\n", true);
+        assert_eq!(
+            errors,
+            ["line 1: Invalid task number. Available tasks: 1-5"]
+        );
+        assert!(!transcript.contains("> 4"));
+
+        let (transcript, errors) = run_script("9\n4\nexit\n", false);
+        assert_eq!(errors.len(), 1);
+        assert!(transcript.contains("> 4"));
     }
 }
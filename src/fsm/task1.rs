@@ -0,0 +1,1703 @@
+use crate::common;
+use crate::common::{errln, outln, ArrayGenerationInputStateMachine, Io, LineReader, Res};
+use crate::dataset;
+use crate::distribution::Distribution;
+use crate::machine::{commands, StateMachine};
+use crate::report::{emit, Format, Json, Record};
+use crate::statistics::Summary;
+use animation::Animation;
+use course_project::task1::introsort::DEFAULT_INSERTION_THRESHOLD;
+use course_project::task1::parallel::{default_threads, DEFAULT_CUTOFF};
+use course_project::task1::{
+    binary_insertion_sort_probed, bottom_up_merge_sort_probed, bucket_sort, bucket_sort_by_key,
+    bucket_sort_probed, count_operations, counting_sort, counting_sort_by_key,
+    counting_sort_probed, heap_sort_probed, insertion_sort_probed, introsort_with_probed,
+    is_stable, lsd_radix_sort, lsd_radix_sort_by_key, lsd_radix_sort_probed, merge_sort_probed,
+    msd_radix_sort, msd_radix_sort_by_key, msd_radix_sort_probed, parallel_merge_sort_with,
+    parallel_merge_sort_with_by, parallel_quicksort_with, parallel_quicksort_with_by,
+    quicksort_with_probed, selection_sort_probed, shell_sort_probed, stable_quicksort_probed,
+    stable_selection_sort_probed, Counted, Counting, Gaps, Operations, Partition, Pivot, Tagged,
+    Traced, Tracing,
+};
+use external::{external_sort, ExternalJob, Memory, DEFAULT_MEMORY, DEFAULT_SORT};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::io::Write;
+use std::time::Instant;
+use sweep::{
+    geometric_sizes, Sweep, SweepResult, DEFAULT_BUDGET, DEFAULT_RANGE, DEFAULT_RUNS,
+    DEFAULT_SIZES, DEFAULT_STEPS, MAX_SIZE,
+};
+
+pub mod animation;
+pub mod export;
+pub mod external;
+pub mod sweep;
+
+#[derive(Debug)]
+enum SortState {
+    Start,
+    AwaitingArray,
+    // `ready` is whether an array was generated before
+    AwaitingSetting { setting: Setting, ready: bool },
+    AwaitingSweep { ready: bool },
+    AwaitingExport { ready: bool },
+    AwaitingExternal { ready: bool },
+    AwaitingLoad { ready: bool },
+    AwaitingSave,
+    AwaitingAnimation { ready: bool },
+    ReadyToWork,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Setting {
+    Gaps,
+    Algorithms,
+    Warmup,
+    Repetitions,
+    Parallelism,
+}
+
+commands! {
+    pub enum SortCommands {
+        Generate => ("generate", "Generate array"),
+        Load => ("load", "Load array from a file"),
+        Save => ("save", "Save array to a file"),
+        Sort => ("sort", "Sort array"),
+        Stat => ("stat", "Print statistics"),
+        Gaps => ("gaps", "Select gap sequence of shell sort"),
+        Algorithms => ("algorithms", "Select sorts to benchmark"),
+        Count => ("count", "Toggle counting of comparisons, swaps and writes"),
+        Records => ("records", "Toggle sorting records instead of integers"),
+        Warmup => ("warmup", "Set untimed runs of every sort before the first iteration"),
+        Repetitions => ("repetitions", "Set iterations run by one 'sort'"),
+        Threads => ("threads", "Set threads and sequential cutoff of the parallel sorts"),
+        Sweep => ("sweep", "Benchmark sorts over growing array sizes"),
+        Export => ("export", "Save results to CSV files and SVG charts"),
+        External => ("external", "Sort a file of integers larger than the memory"),
+        Animate => ("animate", "Render the steps of a sort on a small array as animated SVG"),
+    }
+}
+
+/// Sorts an array in place.
+pub type SortFn = Box<dyn Fn(&mut [i32])>;
+
+/// Sorts an array of elements counting their comparisons in place.
+pub type CountedSortFn = Box<dyn Fn(&mut [Counted<i32>])>;
+
+/// Sorts an array of keys tagged with their original index in place.
+pub type TaggedSortFn = Box<dyn Fn(&mut [Tagged<i32>])>;
+
+/// Sorts records in place.
+pub type RecordSortFn = Box<dyn Fn(&mut [Item])>;
+
+/// Sorts an array of elements recording their comparisons in place.
+pub type TracedSortFn = Box<dyn Fn(&mut [Traced<i32>])>;
+
+/// Untimed runs of every sort before the first iteration of a benchmark.
+pub const DEFAULT_WARMUP: usize = 1;
+
+/// Widest value range counting sort is run for, its counters take 8 bytes each.
+pub const MAX_COUNTING_RANGE: i64 = 1 << 24;
+
+/// Length and number of distinct keys of the array the stability of the sorts is checked on.
+pub const STABILITY_PROBE: (usize, i32) = (1000, 10);
+
+/// Element of the records benchmark, one per value of the array. Records are sorted by descending
+/// score, so the sorts are run through their comparator and key variants.
+#[derive(Debug, Clone)]
+pub struct Item {
+    /// Position of the value in the array.
+    pub id: usize,
+    pub score: i32,
+}
+
+fn items(vec: &[i32]) -> Vec<Item> {
+    vec.iter()
+        .enumerate()
+        .map(|(id, &score)| Item { id, score })
+        .collect()
+}
+
+// scores of the sorted records, checked to hold every record once
+fn sorted_scores(items: &[Item]) -> Vec<i32> {
+    let mut seen = vec![false; items.len()];
+    for item in items {
+        assert!(!seen[item.id], "record {} was duplicated", item.id);
+        seen[item.id] = true;
+    }
+    items.iter().map(|item| item.score).collect()
+}
+
+// orders the records by descending score
+fn compare_items(a: &Item, b: &Item) -> Ordering {
+    b.score.cmp(&a.score)
+}
+
+// key of the records for the integer sorts, `!` reverses the order without overflowing
+fn item_key(item: &Item) -> i32 {
+    !item.score
+}
+
+/// Threads and part length below which the parallel sorts sort sequentially.
+#[derive(Debug, Clone, Copy)]
+pub struct Parallelism {
+    pub threads: usize,
+    pub cutoff: usize,
+}
+
+impl Default for Parallelism {
+    fn default() -> Self {
+        Parallelism {
+            threads: default_threads(),
+            cutoff: DEFAULT_CUTOFF,
+        }
+    }
+}
+
+/// A sort compared by [`SortBenchmark`].
+pub struct Algorithm {
+    /// Column of the iteration table and key of the JSON timings.
+    pub name: String,
+    /// Name used in the statistics.
+    pub title: String,
+    pub sort: SortFn,
+    /// The same sort for counted elements, `None` for sorts that do not compare elements.
+    pub counted: Option<CountedSortFn>,
+    /// The same sort reporting its writes to the counters, for sorts that do not compare elements.
+    pub counted_writes: Option<SortFn>,
+    /// The same sort for tagged keys, `None` for sorts whose stability cannot be observed.
+    pub tagged: Option<TaggedSortFn>,
+    /// The same sort for records, `None` if it cannot sort them.
+    pub records: Option<RecordSortFn>,
+    /// The same sort for traced elements, `None` for sorts whose steps cannot be traced.
+    pub traced: Option<TracedSortFn>,
+    /// Whether the sort reports its swaps and writes, the std sort does not.
+    pub counts_moves: bool,
+    /// Whether the sort can be run on the array, skipped otherwise.
+    pub applicable: fn(&[i32]) -> bool,
+    /// Name of the sequential sort a parallel sort is compared with.
+    pub sequential: Option<&'static str>,
+}
+
+// a comparison sort given as a call of its probed variant, instantiated for plain, counted,
+// tagged and traced elements and for records; only the counted and traced runs get a probe
+macro_rules! comparison_sort {
+    ($name:expr, $title:expr, |$slice:ident, $compare:ident, $probe:ident| $sort:expr $(,)?) => {
+        Algorithm::new($name, $title, move |$slice: &mut [i32]| {
+            let ($compare, $probe) = (i32::cmp, &mut ());
+            $sort
+        })
+        .counted(move |$slice: &mut [Counted<i32>]| {
+            let ($compare, $probe) = (Counted::<i32>::cmp, &mut Counting);
+            $sort
+        })
+        .tagged(move |$slice: &mut [Tagged<i32>]| {
+            let ($compare, $probe) = (Tagged::<i32>::cmp, &mut ());
+            $sort
+        })
+        .records(move |$slice: &mut [Item]| {
+            let ($compare, $probe) = (compare_items, &mut ());
+            $sort
+        })
+        .traced(move |$slice: &mut [Traced<i32>]| {
+            let ($compare, $probe) = (Traced::<i32>::cmp, &mut Tracing);
+            $sort
+        })
+    };
+    ($name:expr, $title:expr, $sort_probed:path $(,)?) => {
+        comparison_sort!($name, $title, |slice, compare, probe| $sort_probed(
+            slice, compare, probe
+        ))
+    };
+}
+
+impl Algorithm {
+    fn new(name: &str, title: &str, sort: impl Fn(&mut [i32]) + 'static) -> Self {
+        Algorithm {
+            name: name.to_string(),
+            title: title.to_string(),
+            sort: Box::new(sort),
+            counted: None,
+            counted_writes: None,
+            tagged: None,
+            records: None,
+            traced: None,
+            counts_moves: true,
+            applicable: |_| true,
+            sequential: None,
+        }
+    }
+
+    fn counted(mut self, sort: impl Fn(&mut [Counted<i32>]) + 'static) -> Self {
+        self.counted = Some(Box::new(sort));
+        self
+    }
+
+    fn counted_writes(mut self, sort: impl Fn(&mut [i32]) + 'static) -> Self {
+        self.counted_writes = Some(Box::new(sort));
+        self
+    }
+
+    fn records(mut self, sort: impl Fn(&mut [Item]) + 'static) -> Self {
+        self.records = Some(Box::new(sort));
+        self
+    }
+
+    fn tagged(mut self, sort: impl Fn(&mut [Tagged<i32>]) + 'static) -> Self {
+        self.tagged = Some(Box::new(sort));
+        self
+    }
+
+    fn traced(mut self, sort: impl Fn(&mut [Traced<i32>]) + 'static) -> Self {
+        self.traced = Some(Box::new(sort));
+        self
+    }
+
+    /// Whether the sort kept the order of equal keys of `probe`, `None` if it does not compare
+    /// elements.
+    fn is_stable(&self, probe: &[i32]) -> Option<bool> {
+        self.tagged
+            .as_ref()
+            .map(|sort| is_stable(probe, |slice| sort(slice)))
+    }
+
+    fn without_move_counts(mut self) -> Self {
+        self.counts_moves = false;
+        // a trace without the moves cannot be replayed
+        self.traced = None;
+        self
+    }
+
+    /// Sorts a copy of `vec` and returns the operations performed, nothing for sorts that report
+    /// none.
+    fn count(&self, vec: &[i32]) -> Operations {
+        match (&self.counted, &self.counted_writes) {
+            (Some(sort), _) => {
+                let mut data = vec.iter().copied().map(Counted).collect::<Vec<_>>();
+                count_operations(|| sort(&mut data))
+            }
+            (None, Some(sort)) => {
+                let mut data = vec.to_vec();
+                count_operations(|| sort(&mut data))
+            }
+            (None, None) => Operations::default(),
+        }
+    }
+
+    // comparisons, swaps and writes, `None` if the sort does not report them
+    fn reported(&self, operations: &Operations) -> [Option<u64>; 3] {
+        let moves = |count| self.counts_moves.then_some(count);
+        [
+            self.counted.is_some().then_some(operations.comparisons),
+            moves(operations.swaps),
+            moves(operations.writes),
+        ]
+    }
+
+    fn only_if(mut self, applicable: fn(&[i32]) -> bool) -> Self {
+        self.applicable = applicable;
+        self
+    }
+
+    fn parallel_version_of(mut self, sequential: &'static str) -> Self {
+        self.sequential = Some(sequential);
+        self
+    }
+}
+
+// `sort_unstable_by` of std as a function
+fn std_sort_by<T>(slice: &mut [T], compare: impl FnMut(&T, &T) -> Ordering) {
+    slice.sort_unstable_by(compare);
+}
+
+fn value_range(slice: &[i32]) -> i64 {
+    match (slice.iter().min(), slice.iter().max()) {
+        (Some(&min), Some(&max)) => max as i64 - min as i64 + 1,
+        _ => 0,
+    }
+}
+
+/// Every sort of task1, starting with `sort_unstable` from std the others are checked against.
+///
+/// Each run of a quicksort with the random pivot draws the pivots from a generator seeded with
+/// `seed`, so a session with the same seed sorts the same way.
+pub fn algorithms(gaps: Gaps, parallelism: Parallelism, seed: u64) -> Vec<Algorithm> {
+    let Parallelism { threads, cutoff } = parallelism;
+    let mut algorithms = vec![
+        comparison_sort!("std", "Std sort", |slice, compare, _probe| std_sort_by(
+            slice, compare
+        ))
+        .without_move_counts(),
+        comparison_sort!("quick", "Quicksort", |slice, compare, probe| {
+            let rng = &mut StdRng::seed_from_u64(seed);
+            quicksort_with_probed(slice, Pivot::First, Partition::TwoWay, rng, compare, probe)
+        }),
+    ];
+    for partition in Partition::ALL {
+        for pivot in Pivot::ALL {
+            let prefix = match partition {
+                Partition::TwoWay if pivot == Pivot::First => continue,
+                Partition::TwoWay => "quick",
+                Partition::ThreeWay => "quick3",
+            };
+            algorithms.push(comparison_sort!(
+                &format!("{}-{}", prefix, pivot),
+                &format!("Quicksort ({} pivot, {} partition)", pivot, partition),
+                |slice, compare, probe| {
+                    let rng = &mut StdRng::seed_from_u64(seed);
+                    quicksort_with_probed(slice, pivot, partition, rng, compare, probe)
+                },
+            ));
+        }
+    }
+    algorithms.extend([
+        comparison_sort!("quick-stable", "Stable quicksort", stable_quicksort_probed),
+        comparison_sort!("intro", "Introsort", |slice, compare, probe| {
+            introsort_with_probed(slice, DEFAULT_INSERTION_THRESHOLD, compare, probe)
+        }),
+        comparison_sort!("select", "Selection sort", selection_sort_probed),
+        comparison_sort!(
+            "select-stable",
+            "Stable selection sort",
+            stable_selection_sort_probed
+        ),
+        comparison_sort!("merge", "Merge sort", merge_sort_probed),
+        comparison_sort!(
+            "merge-bu",
+            "Bottom-up merge sort",
+            bottom_up_merge_sort_probed
+        ),
+        comparison_sort!("heap", "Heap sort", heap_sort_probed),
+        comparison_sort!(
+            "shell",
+            &format!("Shell sort ({} gaps)", gaps),
+            |slice, compare, probe| shell_sort_probed(slice, gaps, compare, probe),
+        ),
+        comparison_sort!("insert", "Insertion sort", insertion_sort_probed),
+        comparison_sort!(
+            "bin-insert",
+            "Binary insertion sort",
+            binary_insertion_sort_probed
+        ),
+        // comparisons and moves in the spawned threads are not counted
+        Algorithm::new(
+            "par-merge",
+            &format!("Parallel merge sort ({} threads)", threads),
+            move |slice| parallel_merge_sort_with(slice, threads, cutoff),
+        )
+        .tagged(move |slice| parallel_merge_sort_with(slice, threads, cutoff))
+        .records(move |items| parallel_merge_sort_with_by(items, threads, cutoff, compare_items))
+        .without_move_counts()
+        .parallel_version_of("merge"),
+        Algorithm::new(
+            "par-quick",
+            &format!("Parallel quicksort ({} threads)", threads),
+            move |slice| parallel_quicksort_with(slice, threads, cutoff),
+        )
+        .tagged(move |slice| parallel_quicksort_with(slice, threads, cutoff))
+        .records(move |items| parallel_quicksort_with_by(items, threads, cutoff, compare_items))
+        .without_move_counts()
+        .parallel_version_of("quick3-median3"),
+        Algorithm::new("counting", "Counting sort", counting_sort)
+            .counted_writes(|slice| counting_sort_probed(slice, &mut Counting))
+            .records(|items| counting_sort_by_key(items, item_key))
+            .only_if(|slice| value_range(slice) <= MAX_COUNTING_RANGE),
+        Algorithm::new("radix-lsd", "LSD radix sort", lsd_radix_sort)
+            .counted_writes(|slice| lsd_radix_sort_probed(slice, &mut Counting))
+            .records(|items| lsd_radix_sort_by_key(items, item_key)),
+        Algorithm::new("radix-msd", "MSD radix sort", msd_radix_sort)
+            .counted_writes(|slice| msd_radix_sort_probed(slice, &mut Counting))
+            .records(|items| msd_radix_sort_by_key(items, item_key)),
+        Algorithm::new("bucket", "Bucket sort", bucket_sort)
+            .counted_writes(|slice| bucket_sort_probed(slice, &mut Counting))
+            .records(|items| bucket_sort_by_key(items, item_key)),
+    ]);
+    algorithms
+}
+
+/// Keeps the algorithms matching any of `patterns`, either a name or a prefix followed by '*'.
+///
+/// The first algorithm is always kept as the reference. Returns the first pattern matching nothing
+/// as the error.
+pub fn select_algorithms(
+    algorithms: Vec<Algorithm>,
+    patterns: &[String],
+) -> Result<Vec<Algorithm>, String> {
+    let matches = |pattern: &str, name: &str| match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == pattern,
+    };
+    if let Some(unknown) = patterns.iter().find(|pattern| {
+        !algorithms
+            .iter()
+            .any(|algorithm| matches(pattern, &algorithm.name))
+    }) {
+        return Err(unknown.clone());
+    }
+    if patterns.is_empty() {
+        return Ok(algorithms);
+    }
+    Ok(algorithms
+        .into_iter()
+        .enumerate()
+        .filter(|(i, algorithm)| {
+            *i == 0
+                || patterns
+                    .iter()
+                    .any(|pattern| matches(pattern, &algorithm.name))
+        })
+        .map(|(_, algorithm)| algorithm)
+        .collect())
+}
+
+pub struct SortBenchmark {
+    vec: Vec<i32>,
+    algorithms: Vec<Algorithm>,
+    iter_number: usize,
+    // seconds per iteration, in the order of `algorithms`, NaN if skipped
+    result: Vec<Vec<f64>>,
+    // whether every iteration also counts the operations of the sorts
+    counting: bool,
+    // operations per iteration, `None` if skipped or not counted
+    operations: Vec<Vec<Option<Operations>>>,
+    // untimed runs of every sort before the first iteration
+    warmup: usize,
+    // whether records are sorted instead of the integers
+    records: bool,
+}
+
+impl SortBenchmark {
+    pub fn new(vec: Vec<i32>, algorithms: Vec<Algorithm>) -> Self {
+        SortBenchmark {
+            vec,
+            algorithms,
+            iter_number: 0,
+            result: vec![],
+            counting: false,
+            operations: vec![],
+            warmup: DEFAULT_WARMUP,
+            records: false,
+        }
+    }
+
+    /// Sorts records holding the values by descending score instead of the values, the integer
+    /// sorts use the score as the key. Operations and stability are still checked on the values.
+    pub fn with_records(mut self, records: bool) -> Self {
+        self.records = records;
+        self
+    }
+
+    // whether the algorithm is run on the array in the current mode
+    fn runs(&self, algorithm: &Algorithm) -> bool {
+        (algorithm.applicable)(&self.vec) && (!self.records || algorithm.records.is_some())
+    }
+
+    // seconds taken to sort a copy of the values or of `items`, and the sorted values or scores
+    fn time(&self, algorithm: &Algorithm, items: Option<&[Item]>) -> (f64, Vec<i32>) {
+        match (items, &algorithm.records) {
+            (Some(items), Some(sort)) => {
+                let mut data = items.to_vec();
+                let now = Instant::now();
+                sort(&mut data);
+                (now.elapsed().as_secs_f64(), sorted_scores(&data))
+            }
+            _ => {
+                let mut data = self.vec.clone();
+                let now = Instant::now();
+                (algorithm.sort)(&mut data);
+                (now.elapsed().as_secs_f64(), data)
+            }
+        }
+    }
+
+    /// Runs every sort `warmup` times without timing it before the first iteration, so caches and
+    /// the allocator are warmed up.
+    pub fn with_warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    /// Counts comparisons, swaps and writes in the following iterations. The counted run is
+    /// separate from the timed one and only it reports to the counters, so the timings are not
+    /// affected.
+    pub fn with_counting(mut self, counting: bool) -> Self {
+        self.counting = counting;
+        self
+    }
+
+    pub fn work(&mut self, out: &mut impl Write, format: Format) {
+        let mut expected: Option<Vec<i32>> = None;
+        let mut times = vec![];
+        let mut operations = vec![];
+        let items = self.records.then(|| items(&self.vec));
+
+        if self.iter_number == 0 {
+            for algorithm in &self.algorithms {
+                if self.runs(algorithm) {
+                    for _ in 0..self.warmup {
+                        self.time(algorithm, items.as_deref());
+                    }
+                }
+            }
+        }
+        for algorithm in &self.algorithms {
+            if !self.runs(algorithm) {
+                times.push(f64::NAN);
+                operations.push(None);
+                continue;
+            }
+            operations.push(self.counting.then(|| algorithm.count(&self.vec)));
+            // unstable sorts may order records of equal score differently, so only the scores
+            // are compared
+            let (time, data) = self.time(algorithm, items.as_deref());
+            times.push(time);
+            match &expected {
+                Some(expected) => assert_eq!(&data, expected, "{} failed", algorithm.title),
+                None => expected = Some(data),
+            }
+        }
+
+        let mut record = self.algorithms.iter().zip(&times).fold(
+            Record::new("sort", "sort")
+                .input("size", self.vec.len())
+                .input("records", self.records)
+                .input("iteration", self.iter_number)
+                .result("sorted", true),
+            |record, (algorithm, time)| record.timing(&algorithm.name, *time),
+        );
+        if self.counting {
+            let counts = self.reported(&operations, |count| count.map(|count| count as f64));
+            record = record.result("operations", operations_json(&self.algorithms, &counts));
+        }
+        emit(out, format, record, |out| {
+            let names = self
+                .algorithms
+                .iter()
+                .map(|algorithm| algorithm.name.as_str())
+                .collect::<Vec<&str>>();
+            let times = times
+                .iter()
+                .map(|&time| format_time(time))
+                .collect::<Vec<String>>();
+            outln!(out, "Iteration, {}", names.join(", "));
+            outln!(out, "{}, {}", self.iter_number, times.join(", "));
+            if self.counting {
+                let counts = self.reported(&operations, |count| count);
+                for (kind, label) in OPERATION_LABELS.iter().enumerate() {
+                    let counts = counts
+                        .iter()
+                        .map(|count| count[kind].map_or("-".to_string(), |count| count.to_string()))
+                        .collect::<Vec<String>>();
+                    outln!(out, "{}, {}", label, counts.join(", "));
+                }
+            }
+        });
+        self.iter_number += 1;
+        self.result.push(times);
+        self.operations.push(operations);
+    }
+
+    // the operations every algorithm reports, converted by `convert`
+    fn reported<T>(
+        &self,
+        operations: &[Option<Operations>],
+        convert: impl Fn(Option<u64>) -> T,
+    ) -> Vec<[T; 3]> {
+        self.algorithms
+            .iter()
+            .zip(operations)
+            .map(|(algorithm, operations)| {
+                let reported = match operations {
+                    Some(operations) => algorithm.reported(operations),
+                    None => [None; 3],
+                };
+                reported.map(&convert)
+            })
+            .collect()
+    }
+
+    // average operations of every algorithm over the iterations they were counted in
+    fn average_operations(&self) -> Option<Vec<[Option<f64>; 3]>> {
+        let counted = self
+            .operations
+            .iter()
+            .filter(|operations| operations.iter().any(Option::is_some))
+            .collect::<Vec<_>>();
+        if counted.is_empty() {
+            return None;
+        }
+        let averages = self
+            .algorithms
+            .iter()
+            .enumerate()
+            .map(|(i, algorithm)| {
+                let counts = counted
+                    .iter()
+                    .filter_map(|operations| operations[i].as_ref())
+                    .map(|operations| algorithm.reported(operations))
+                    .collect::<Vec<[Option<u64>; 3]>>();
+                [0, 1, 2].map(|kind| {
+                    let values = counts
+                        .iter()
+                        .filter_map(|count| count[kind].map(|count| count as f64))
+                        .collect::<Vec<f64>>();
+                    (!values.is_empty()).then(|| common::find_average(&values))
+                })
+            })
+            .collect();
+        Some(averages)
+    }
+
+    /// Statistics of the times of every algorithm, `None` if it was always skipped.
+    pub fn summaries(&self) -> Vec<Option<Summary>> {
+        (0..self.algorithms.len())
+            .map(|i| {
+                let times = self
+                    .result
+                    .iter()
+                    .map(|x| x[i])
+                    .filter(|time| !time.is_nan())
+                    .collect::<Vec<f64>>();
+                Summary::of(&times)
+            })
+            .collect()
+    }
+
+    /// Whether every algorithm kept the order of equal keys of a fixed array with many duplicates,
+    /// `None` if it does not compare elements.
+    pub fn stability(&self) -> Vec<Option<bool>> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let probe = (0..STABILITY_PROBE.0)
+            .map(|_| rng.gen_range(0..STABILITY_PROBE.1))
+            .collect::<Vec<i32>>();
+        self.algorithms
+            .iter()
+            .map(|algorithm| algorithm.is_stable(&probe))
+            .collect()
+    }
+
+    pub fn print_stat(&self, out: &mut impl Write, format: Format) {
+        let summaries = self.summaries();
+        let stability = self.stability();
+        let means = summaries
+            .iter()
+            .map(|summary| summary.map_or(f64::NAN, |summary| summary.mean))
+            .collect::<Vec<f64>>();
+        // the first algorithm is the reference
+        let reference = summaries[0];
+        let relative = means
+            .iter()
+            .map(|mean| means[0] / mean)
+            .collect::<Vec<f64>>();
+        let faster = self
+            .algorithms
+            .iter()
+            .zip(&means)
+            .skip(1)
+            .filter(|(_, &mean)| mean < means[0])
+            .map(|(algorithm, _)| algorithm.name.as_str())
+            .collect::<Vec<&str>>();
+        let significantly_faster = self
+            .algorithms
+            .iter()
+            .zip(&summaries)
+            .skip(1)
+            .filter(|(_, summary)| match (summary, reference) {
+                (Some(summary), Some(reference)) => summary.ci_high < reference.ci_low,
+                _ => false,
+            })
+            .map(|(algorithm, _)| algorithm.name.as_str())
+            .collect::<Vec<&str>>();
+        // speedup of every parallel sort over its sequential version, if benchmarked, and over std
+        let speedups = self
+            .algorithms
+            .iter()
+            .zip(&means)
+            .filter_map(|(algorithm, &mean)| {
+                let sequential = algorithm.sequential?;
+                let over_sequential = self
+                    .algorithms
+                    .iter()
+                    .zip(&means)
+                    .find(|(other, _)| other.name == sequential)
+                    .map(|(_, &sequential_mean)| sequential_mean / mean);
+                Some((algorithm, sequential, over_sequential, means[0] / mean))
+            })
+            .collect::<Vec<_>>();
+        let statistics = Json::Object(
+            self.algorithms
+                .iter()
+                .zip(&summaries)
+                .zip(&relative)
+                .zip(&stability)
+                .map(|(((algorithm, summary), &relative), &stable)| {
+                    let mut fields: Vec<(String, Json)> = match summary {
+                        Some(summary) => Summary::COLUMNS
+                            .iter()
+                            .zip(summary.values())
+                            .map(|(column, value)| (column.to_string(), value.into()))
+                            .chain([
+                                ("runs".to_string(), summary.count.into()),
+                                ("relative_to_std".to_string(), relative.into()),
+                            ])
+                            .collect(),
+                        None => vec![("runs".to_string(), 0usize.into())],
+                    };
+                    fields.push(("stable".to_string(), stable.into()));
+                    (algorithm.name.clone(), Json::Object(fields))
+                })
+                .collect(),
+        );
+        let operations = self.average_operations();
+        let mut record = self.algorithms.iter().zip(&means).fold(
+            Record::new("sort", "stat")
+                .input("size", self.vec.len())
+                .input("iterations", self.iter_number)
+                .input("warmup", self.warmup)
+                .input("records", self.records)
+                .result("faster_than_std", faster.clone())
+                .result(
+                    "significantly_faster_than_std",
+                    significantly_faster.clone(),
+                )
+                .result("statistics", statistics)
+                .result(
+                    "parallel_speedups",
+                    Json::Object(
+                        speedups
+                            .iter()
+                            .map(|(algorithm, sequential, over_sequential, over_std)| {
+                                let fields = vec![
+                                    ("sequential".to_string(), (*sequential).into()),
+                                    ("over_sequential".to_string(), (*over_sequential).into()),
+                                    ("over_std".to_string(), (*over_std).into()),
+                                ];
+                                (algorithm.name.clone(), Json::Object(fields))
+                            })
+                            .collect(),
+                    ),
+                ),
+            |record, (algorithm, mean)| record.timing(&algorithm.name, *mean),
+        );
+        if let Some(operations) = &operations {
+            record = record.result("operations", operations_json(&self.algorithms, operations));
+        }
+        emit(out, format, record, |out| {
+            outln!(
+                out,
+                "Times of {} iterations{} after {} warm-up runs in seconds, \
+                ci is the 95% confidence interval of the mean:",
+                self.iter_number,
+                match self.records {
+                    true => " sorting records by descending score",
+                    false => "",
+                },
+                self.warmup
+            );
+            outln!(
+                out,
+                "Sort, runs, {}, vs std, stable",
+                Summary::COLUMNS.join(", ")
+            );
+            for (((algorithm, summary), relative), stable) in self
+                .algorithms
+                .iter()
+                .zip(&summaries)
+                .zip(&relative)
+                .zip(&stability)
+            {
+                let (runs, values) = match summary {
+                    Some(summary) => (summary.count, summary.values()),
+                    None => (0, [f64::NAN; 11]),
+                };
+                let values = values
+                    .iter()
+                    .map(|&value| format_time(value))
+                    .collect::<Vec<String>>();
+                let relative = match relative.is_nan() {
+                    true => "-".to_string(),
+                    false => format!("{:.2}x", relative),
+                };
+                outln!(
+                    out,
+                    "{}, {}, {}, {}, {}",
+                    algorithm.name,
+                    runs,
+                    values.join(", "),
+                    relative,
+                    format_stable(*stable)
+                );
+            }
+            match faster.is_empty() {
+                true => outln!(out, "No sort is faster than std sort"),
+                false => outln!(out, "Faster than std sort: {}", faster.join(", ")),
+            }
+            if !significantly_faster.is_empty() {
+                outln!(
+                    out,
+                    "Significantly faster than std sort (confidence intervals do not overlap): {}",
+                    significantly_faster.join(", ")
+                );
+            }
+            if !speedups.is_empty() {
+                let speedups = speedups
+                    .iter()
+                    .map(|(algorithm, sequential, over_sequential, over_std)| {
+                        let over_sequential = match over_sequential {
+                            Some(speedup) => format!("{:.2}x over {}", speedup, sequential),
+                            None => format!("{} not benchmarked", sequential),
+                        };
+                        format!(
+                            "{}: {}, {:.2}x over std",
+                            algorithm.name, over_sequential, over_std
+                        )
+                    })
+                    .collect::<Vec<String>>();
+                outln!(out, "Parallel speedup: {}", speedups.join("; "));
+            }
+            if let Some(operations) = &operations {
+                let averages = self
+                    .algorithms
+                    .iter()
+                    .zip(operations)
+                    .filter_map(|(algorithm, counts)| {
+                        let counts = OPERATION_LABELS
+                            .iter()
+                            .zip(counts)
+                            .filter_map(|(label, count)| {
+                                count.map(|count| format!("{:.0} {}", count, label.to_lowercase()))
+                            })
+                            .collect::<Vec<String>>();
+                        (!counts.is_empty())
+                            .then(|| format!("{}: {}", algorithm.title, counts.join(", ")))
+                    })
+                    .collect::<Vec<String>>();
+                outln!(out, "Average operations: {}", averages.join("; "));
+            }
+        });
+    }
+}
+
+const OPERATION_LABELS: [&str; 3] = ["Comparisons", "Swaps", "Writes"];
+
+// operations of every algorithm keyed by its name, counts that are not reported are null
+fn operations_json(algorithms: &[Algorithm], counts: &[[Option<f64>; 3]]) -> Json {
+    Json::Object(
+        algorithms
+            .iter()
+            .zip(counts)
+            .map(|(algorithm, counts)| {
+                let fields = OPERATION_LABELS
+                    .iter()
+                    .zip(counts)
+                    .map(|(label, &count)| (label.to_lowercase(), count.into()))
+                    .collect();
+                (algorithm.name.clone(), Json::Object(fields))
+            })
+            .collect(),
+    )
+}
+
+/// Splits a comma-separated list of algorithm patterns.
+pub fn parse_patterns(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// sorts that do not compare elements are shown as '-'
+fn format_stable(stable: Option<bool>) -> &'static str {
+    match stable {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "-",
+    }
+}
+
+// skipped runs are shown as '-'
+fn format_time(seconds: f64) -> String {
+    match seconds.is_nan() {
+        true => "-".to_string(),
+        false => format!("{:.10}", seconds),
+    }
+}
+
+pub struct SortStateMachine<'a, R: LineReader, W: Write> {
+    state: SortState,
+    io: &'a mut Io<R, W>,
+    gaps: Gaps,
+    parallelism: Parallelism,
+    // patterns of `select_algorithms`, all algorithms if empty
+    patterns: Vec<String>,
+    // count the operations of the sorts in every iteration
+    counting: bool,
+    // sort records instead of the integers
+    records: bool,
+    warmup: usize,
+    // iterations run by one 'sort' command
+    repetitions: usize,
+    benchmark: SortBenchmark,
+    last_sweep: Option<SweepResult>,
+}
+
+impl<'a, R: LineReader, W: Write> SortStateMachine<'a, R, W> {
+    pub fn new(io: &'a mut Io<R, W>) -> Self {
+        SortStateMachine {
+            state: SortState::Start,
+            io,
+            gaps: Gaps::Ciura,
+            parallelism: Parallelism::default(),
+            patterns: vec![],
+            counting: false,
+            records: false,
+            warmup: DEFAULT_WARMUP,
+            repetitions: 1,
+            benchmark: SortBenchmark::new(vec![], vec![]),
+            last_sweep: None,
+        }
+    }
+
+    fn lineup(&self) -> Vec<Algorithm> {
+        select_algorithms(
+            algorithms(self.gaps, self.parallelism, self.io.seed()),
+            &self.patterns,
+        )
+        .expect("patterns are checked when entered")
+    }
+
+    fn handle_setting(&mut self, input: &str, setting: Setting, ready: bool) -> Res<()> {
+        let input = input.trim();
+        let (record, message) = match setting {
+            Setting::Gaps => match input.parse::<Gaps>() {
+                Ok(gaps) => {
+                    self.gaps = gaps;
+                    self.lineup_report(Record::new("sort", "gaps").input("gaps", gaps.name()))
+                }
+                Err(_) => {
+                    let names = Gaps::ALL.map(|gaps| gaps.name());
+                    errln!(
+                        self.io,
+                        "Unknown gap sequence. Available: {}",
+                        names.join(", ")
+                    );
+                    return Res::Repeat;
+                }
+            },
+            Setting::Algorithms => {
+                let patterns = parse_patterns(input);
+                match select_algorithms(
+                    algorithms(self.gaps, self.parallelism, self.io.seed()),
+                    &patterns,
+                ) {
+                    Ok(_) => {
+                        self.patterns = patterns;
+                        self.lineup_report(
+                            Record::new("sort", "algorithms").input("algorithms", input),
+                        )
+                    }
+                    Err(unknown) => {
+                        errln!(self.io, "Unknown algorithm '{}'", unknown);
+                        return Res::Repeat;
+                    }
+                }
+            }
+            Setting::Warmup => match input.parse::<usize>() {
+                Ok(warmup) => {
+                    self.warmup = warmup;
+                    let record = Record::new("sort", "warmup").input("warmup", warmup);
+                    let message = format!(
+                        "Every sort is run {} times before the first iteration",
+                        warmup
+                    );
+                    (record, message)
+                }
+                Err(_) => {
+                    errln!(self.io, "Warm-up runs must be a non-negative number");
+                    return Res::Repeat;
+                }
+            },
+            Setting::Parallelism => {
+                let values = input
+                    .split_whitespace()
+                    .map(str::parse::<usize>)
+                    .collect::<Result<Vec<usize>, _>>();
+                let parallelism = match values.as_deref() {
+                    Ok(&[threads]) if threads > 0 => Parallelism {
+                        threads,
+                        ..self.parallelism
+                    },
+                    Ok(&[threads, cutoff]) if threads > 0 => Parallelism { threads, cutoff },
+                    _ => {
+                        errln!(
+                            self.io,
+                            "Expected a positive number of threads and optionally the cutoff"
+                        );
+                        return Res::Repeat;
+                    }
+                };
+                self.parallelism = parallelism;
+                self.lineup_report(
+                    Record::new("sort", "threads")
+                        .input("threads", parallelism.threads)
+                        .input("cutoff", parallelism.cutoff),
+                )
+            }
+            Setting::Repetitions => match input.parse::<usize>() {
+                Ok(repetitions) if repetitions > 0 => {
+                    self.repetitions = repetitions;
+                    let record =
+                        Record::new("sort", "repetitions").input("repetitions", repetitions);
+                    let message = format!("'sort' runs {} iterations", repetitions);
+                    (record, message)
+                }
+                _ => {
+                    errln!(self.io, "Repetitions must be a positive number");
+                    return Res::Repeat;
+                }
+            },
+        };
+
+        let format = self.io.format();
+        emit(self.io, format, record, |out| outln!(out, "{}", message));
+        if ready {
+            // the repetitions only apply to the following 'sort' commands, other settings
+            // start new statistics
+            if !matches!(setting, Setting::Repetitions) {
+                let vec = std::mem::take(&mut self.benchmark.vec);
+                self.benchmark = self.new_benchmark(vec);
+            }
+            self.state = SortState::ReadyToWork;
+        } else {
+            self.state = SortState::Start;
+        }
+        Res::Repeat
+    }
+
+    // adds the benchmarked sorts to the record of a setting changing them
+    fn lineup_report(&self, record: Record) -> (Record, String) {
+        let names = self
+            .lineup()
+            .into_iter()
+            .map(|algorithm| algorithm.name)
+            .collect::<Vec<String>>();
+        let message = format!("Benchmarked sorts: {}", names.join(", "));
+        (record.result("algorithms", names), message)
+    }
+
+    fn handle_sweep(&mut self, input: &str, ready: bool) -> Res<()> {
+        let values = input
+            .split_whitespace()
+            .map(str::parse::<f64>)
+            .collect::<Result<Vec<f64>, _>>();
+        let (from, to, budget) = match values.as_deref() {
+            Ok(&[]) => (DEFAULT_SIZES.0, DEFAULT_SIZES.1, DEFAULT_BUDGET),
+            Ok(&[from, to]) if from >= 1.0 && to >= from && to <= MAX_SIZE as f64 => {
+                (from as usize, to as usize, DEFAULT_BUDGET)
+            }
+            Ok(&[from, to, budget])
+                if from >= 1.0 && to >= from && to <= MAX_SIZE as f64 && budget > 0.0 =>
+            {
+                (from as usize, to as usize, budget)
+            }
+            _ => {
+                errln!(
+                    self.io,
+                    "Expected two sizes up to {}, the smallest first, and a positive time budget",
+                    MAX_SIZE
+                );
+                return Res::Repeat;
+            }
+        };
+        let sweep = Sweep {
+            sizes: geometric_sizes(from, to, DEFAULT_STEPS),
+            left: DEFAULT_RANGE.0,
+            right: DEFAULT_RANGE.1,
+            distribution: Distribution::Uniform,
+            budget,
+            runs: DEFAULT_RUNS,
+        };
+        // seeded from the session, which cannot be borrowed together with its output
+        let mut rng = StdRng::seed_from_u64(self.io.rng().gen());
+        let format = self.io.format();
+        self.last_sweep = Some(sweep.run(self.io, format, &self.lineup(), &mut rng));
+        self.state = match ready {
+            true => SortState::ReadyToWork,
+            false => SortState::Start,
+        };
+        Res::Repeat
+    }
+
+    fn handle_export(&mut self, input: &str, ready: bool) -> Res<()> {
+        let prefix = input.trim();
+        if prefix.is_empty() {
+            errln!(self.io, "File prefix must not be empty");
+            return Res::Repeat;
+        }
+        let format = self.io.format();
+        if self.benchmark.iter_number > 0 {
+            let saved = export::save_benchmark(&self.benchmark, prefix);
+            // the error was reported, the menu goes on
+            let _ = export::report_export(self.io, format, prefix, saved);
+        }
+        if let Some(sweep) = &self.last_sweep {
+            let saved = export::save_sweep(sweep, prefix);
+            // the error was reported, the menu goes on
+            let _ = export::report_export(self.io, format, prefix, saved);
+        }
+        self.state = match ready {
+            true => SortState::ReadyToWork,
+            false => SortState::Start,
+        };
+        Res::Repeat
+    }
+
+    fn handle_load(&mut self, input: &str, ready: bool) -> Res<()> {
+        let path = input.trim();
+        if path.is_empty() || path == dataset::STDIN {
+            errln!(
+                self.io,
+                "Enter the path of a file, the input holds the menu commands"
+            );
+            return Res::Repeat;
+        }
+        let format = self.io.format();
+        self.state = match dataset::report_load(self.io, format, "sort", path) {
+            Some(vec) => {
+                self.benchmark = self.new_benchmark(vec);
+                SortState::ReadyToWork
+            }
+            None if ready => SortState::ReadyToWork,
+            None => SortState::Start,
+        };
+        Res::Repeat
+    }
+
+    fn handle_save(&mut self, input: &str) -> Res<()> {
+        let path = input.trim();
+        if path.is_empty() {
+            errln!(self.io, "File path must not be empty");
+            return Res::Repeat;
+        }
+        let format = self.io.format();
+        dataset::report_save(self.io, format, "sort", path, &self.benchmark.vec);
+        self.state = SortState::ReadyToWork;
+        Res::Repeat
+    }
+
+    fn handle_animation(&mut self, input: &str, ready: bool) -> Res<()> {
+        let (name, path) = match input.split_whitespace().collect::<Vec<&str>>()[..] {
+            [name] => (name, animation::DEFAULT_OUTPUT),
+            [name, path] => (name, path),
+            _ => {
+                errln!(self.io, "Expected a sort and optionally the output file");
+                return Res::Repeat;
+            }
+        };
+        let animated = match Animation::of(
+            algorithms(self.gaps, self.parallelism, self.io.seed()),
+            name,
+        ) {
+            Ok(animated) => animated,
+            Err(message) => {
+                errln!(self.io, "{}", message);
+                return Res::Repeat;
+            }
+        };
+        let vec = match ready && self.benchmark.vec.len() <= animation::MAX_SIZE {
+            true => self.benchmark.vec.clone(),
+            false => Distribution::Uniform.generate(
+                self.io.rng(),
+                animation::DEFAULT_SIZE,
+                animation::DEFAULT_RANGE.0,
+                animation::DEFAULT_RANGE.1,
+            ),
+        };
+        let format = self.io.format();
+        // the error was reported, the menu goes on
+        let _ = animated.run(self.io, format, &vec, path, animation::DEFAULT_STEP_SECONDS);
+        self.state = match ready {
+            true => SortState::ReadyToWork,
+            false => SortState::Start,
+        };
+        Res::Repeat
+    }
+
+    fn handle_external(&mut self, input: &str, ready: bool) -> Res<()> {
+        let words = input.split_whitespace().collect::<Vec<&str>>();
+        let (files, rest) = match words.split_at_checked(2) {
+            Some(split) => split,
+            None => {
+                errln!(self.io, "Expected input and output file");
+                return Res::Repeat;
+            }
+        };
+        let (memory, name) = match rest {
+            [] => (Ok(DEFAULT_MEMORY), DEFAULT_SORT),
+            [memory] => (memory.parse::<Memory>(), DEFAULT_SORT),
+            [memory, name] => (memory.parse::<Memory>(), *name),
+            _ => {
+                errln!(
+                    self.io,
+                    "Expected at most a memory budget and a sort after the files"
+                );
+                return Res::Repeat;
+            }
+        };
+        let memory = match memory {
+            Ok(memory) => memory,
+            Err(err) => {
+                errln!(self.io, "{}", err);
+                return Res::Repeat;
+            }
+        };
+        let algorithm = match algorithms(self.gaps, self.parallelism, self.io.seed())
+            .into_iter()
+            .find(|algorithm| algorithm.name == name)
+        {
+            Some(algorithm) => algorithm,
+            None => {
+                errln!(self.io, "Unknown sort '{}'", name);
+                return Res::Repeat;
+            }
+        };
+        let format = self.io.format();
+        let job = ExternalJob::new(files[0], files[1], memory);
+        // the error was reported, the menu goes on
+        let _ = external_sort(self.io, format, &job, algorithm);
+        self.state = match ready {
+            true => SortState::ReadyToWork,
+            false => SortState::Start,
+        };
+        Res::Repeat
+    }
+
+    fn new_benchmark(&self, vec: Vec<i32>) -> SortBenchmark {
+        SortBenchmark::new(vec, self.lineup())
+            .with_counting(self.counting)
+            .with_warmup(self.warmup)
+            .with_records(self.records)
+    }
+
+    fn handle_generate(&mut self) -> Res<()> {
+        let res = ArrayGenerationInputStateMachine::new(self.io)
+            .with_distributions()
+            .start()
+            .nested(|vec| {
+                self.benchmark = self.new_benchmark(vec);
+                self.state = SortState::ReadyToWork;
+                Res::Repeat
+            });
+        if let SortState::AwaitingArray = self.state {
+            self.state = SortState::Start;
+        }
+        res
+    }
+}
+
+impl<'a, R: LineReader, W: Write> StateMachine<R, W> for SortStateMachine<'a, R, W> {
+    type Command = SortCommands;
+    type Output = ();
+
+    fn io(&mut self) -> &mut Io<R, W> {
+        self.io
+    }
+
+    fn commands(&self) -> Vec<SortCommands> {
+        match self.state {
+            SortState::Start => vec![
+                SortCommands::Generate,
+                SortCommands::Load,
+                SortCommands::Gaps,
+                SortCommands::Algorithms,
+                SortCommands::Count,
+                SortCommands::Records,
+                SortCommands::Warmup,
+                SortCommands::Repetitions,
+                SortCommands::Threads,
+                SortCommands::Sweep,
+                SortCommands::Export,
+                SortCommands::External,
+                SortCommands::Animate,
+            ],
+            SortState::AwaitingArray
+            | SortState::AwaitingSetting { .. }
+            | SortState::AwaitingSweep { .. }
+            | SortState::AwaitingExport { .. }
+            | SortState::AwaitingExternal { .. }
+            | SortState::AwaitingLoad { .. }
+            | SortState::AwaitingSave
+            | SortState::AwaitingAnimation { .. } => vec![],
+            SortState::ReadyToWork => vec![
+                SortCommands::Sort,
+                SortCommands::Stat,
+                SortCommands::Load,
+                SortCommands::Save,
+                SortCommands::Gaps,
+                SortCommands::Algorithms,
+                SortCommands::Count,
+                SortCommands::Records,
+                SortCommands::Warmup,
+                SortCommands::Repetitions,
+                SortCommands::Threads,
+                SortCommands::Sweep,
+                SortCommands::Export,
+                SortCommands::External,
+                SortCommands::Animate,
+            ],
+        }
+    }
+
+    fn back(&mut self) -> Res<()> {
+        match self.state {
+            SortState::ReadyToWork => {
+                self.state = SortState::Start;
+                Res::Repeat
+            }
+            SortState::AwaitingSetting { ready: true, .. }
+            | SortState::AwaitingSweep { ready: true }
+            | SortState::AwaitingExport { ready: true }
+            | SortState::AwaitingExternal { ready: true }
+            | SortState::AwaitingLoad { ready: true }
+            | SortState::AwaitingSave
+            | SortState::AwaitingAnimation { ready: true } => {
+                self.state = SortState::ReadyToWork;
+                Res::Repeat
+            }
+            SortState::AwaitingSetting { ready: false, .. }
+            | SortState::AwaitingSweep { ready: false }
+            | SortState::AwaitingExport { ready: false }
+            | SortState::AwaitingExternal { ready: false }
+            | SortState::AwaitingLoad { ready: false }
+            | SortState::AwaitingAnimation { ready: false } => {
+                self.state = SortState::Start;
+                Res::Repeat
+            }
+            SortState::Start | SortState::AwaitingArray => Res::Back,
+        }
+    }
+
+    fn print(&mut self) {
+        match self.state {
+            SortState::Start => {
+                let commands = self.commands_text();
+                outln!(self.io, "Available commands:\n{}", commands);
+            }
+            SortState::ReadyToWork => {
+                outln!(
+                    self.io,
+                    "Array with size {} is ready. Current iteration: {}",
+                    self.benchmark.vec.len(),
+                    self.benchmark.iter_number
+                );
+                let commands = self.commands_text();
+                outln!(self.io, "Available commands:\n{}", commands);
+            }
+            SortState::AwaitingSetting {
+                setting: Setting::Gaps,
+                ..
+            } => {
+                let names = Gaps::ALL.map(|gaps| gaps.name());
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter gap sequence of shell sort ({}, current: {}) or\n{}",
+                    names.join(", "),
+                    self.gaps,
+                    commands
+                );
+            }
+            SortState::AwaitingSetting {
+                setting: Setting::Algorithms,
+                ..
+            } => {
+                let names = algorithms(self.gaps, self.parallelism, self.io.seed())
+                    .into_iter()
+                    .map(|algorithm| algorithm.name)
+                    .collect::<Vec<String>>();
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Available sorts: {}\n\
+                    Enter comma-separated names of the sorts to benchmark, 'name*' selects all sorts \
+                    starting with 'name' and '*' all sorts, or\n{}",
+                    names.join(", "),
+                    commands
+                );
+            }
+            SortState::AwaitingSetting {
+                setting: Setting::Warmup,
+                ..
+            } => {
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter number of untimed runs of every sort before the first iteration \
+                    (current: {}) or\n{}",
+                    self.warmup,
+                    commands
+                );
+            }
+            SortState::AwaitingSetting {
+                setting: Setting::Repetitions,
+                ..
+            } => {
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter number of iterations run by one 'sort' (current: {}) or\n{}",
+                    self.repetitions,
+                    commands
+                );
+            }
+            SortState::AwaitingSetting {
+                setting: Setting::Parallelism,
+                ..
+            } => {
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter number of threads of the parallel sorts and optionally the part length \
+                    below which they sort sequentially (current: {} threads, cutoff {}) or\n{}",
+                    self.parallelism.threads,
+                    self.parallelism.cutoff,
+                    commands
+                );
+            }
+            SortState::AwaitingSweep { .. } => {
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter smallest and largest array size and optionally the time budget of a run \
+                    in seconds, e.g. '10 100000 0.5', empty for {} to {} and {} s, or\n{}",
+                    DEFAULT_SIZES.0,
+                    DEFAULT_SIZES.1,
+                    DEFAULT_BUDGET,
+                    commands
+                );
+            }
+            SortState::AwaitingExport { .. } => {
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter path prefix of the exported files, e.g. 'results/run1', or\n{}",
+                    commands
+                );
+            }
+            SortState::AwaitingLoad { .. } => {
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter file to load the array from, files ending with '.bin' hold little-endian \
+                    32-bit integers, other files numbers separated by whitespace, commas or line \
+                    breaks, or\n{}",
+                    commands
+                );
+            }
+            SortState::AwaitingSave => {
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter file to save the array to, binary if it ends with '.bin', one number per \
+                    line otherwise, or\n{}",
+                    commands
+                );
+            }
+            SortState::AwaitingAnimation { .. } => {
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter sort to animate and optionally the output file, e.g. 'quick3-median3 \
+                    quick.svg' (default: {}); the current array is used if it has at most {} \
+                    elements, a random one of {} otherwise, or\n{}",
+                    animation::DEFAULT_OUTPUT,
+                    animation::MAX_SIZE,
+                    animation::DEFAULT_SIZE,
+                    commands
+                );
+            }
+            SortState::AwaitingExternal { .. } => {
+                let commands = self.commands_text();
+                outln!(
+                    self.io,
+                    "Enter input and output file and optionally the memory budget in bytes, with \
+                    suffix K, M or G, and the sort of the runs, e.g. 'data.txt sorted.txt 16M heap' \
+                    (default: 64M and {}); files ending with '.bin' hold little-endian \
+                    32-bit integers, other files numbers separated by whitespace or commas, or\n{}",
+                    DEFAULT_SORT,
+                    commands
+                );
+            }
+            SortState::AwaitingArray => (),
+        }
+    }
+
+    fn handle_input(&mut self, input: &str, command: Option<SortCommands>) -> Res<()> {
+        match (&self.state, command) {
+            (SortState::AwaitingSetting { setting, ready }, _) => {
+                let (setting, ready) = (*setting, *ready);
+                self.handle_setting(input, setting, ready)
+            }
+            (
+                SortState::Start | SortState::ReadyToWork,
+                Some(
+                    command @ (SortCommands::Gaps
+                    | SortCommands::Algorithms
+                    | SortCommands::Warmup
+                    | SortCommands::Repetitions
+                    | SortCommands::Threads),
+                ),
+            ) => {
+                let setting = match command {
+                    SortCommands::Gaps => Setting::Gaps,
+                    SortCommands::Algorithms => Setting::Algorithms,
+                    SortCommands::Warmup => Setting::Warmup,
+                    SortCommands::Threads => Setting::Parallelism,
+                    _ => Setting::Repetitions,
+                };
+                let ready = matches!(self.state, SortState::ReadyToWork);
+                self.state = SortState::AwaitingSetting { setting, ready };
+                Res::Repeat
+            }
+            (SortState::AwaitingSweep { ready }, _) => {
+                let ready = *ready;
+                self.handle_sweep(input, ready)
+            }
+            (SortState::Start | SortState::ReadyToWork, Some(SortCommands::Sweep)) => {
+                let ready = matches!(self.state, SortState::ReadyToWork);
+                self.state = SortState::AwaitingSweep { ready };
+                Res::Repeat
+            }
+            (SortState::AwaitingExport { ready }, _) => {
+                let ready = *ready;
+                self.handle_export(input, ready)
+            }
+            (SortState::Start | SortState::ReadyToWork, Some(SortCommands::Export)) => {
+                if self.benchmark.iter_number == 0 && self.last_sweep.is_none() {
+                    errln!(self.io, "Nothing to export, run 'sort' or 'sweep' first");
+                    return Res::Repeat;
+                }
+                let ready = matches!(self.state, SortState::ReadyToWork);
+                self.state = SortState::AwaitingExport { ready };
+                Res::Repeat
+            }
+            (SortState::AwaitingLoad { ready }, _) => {
+                let ready = *ready;
+                self.handle_load(input, ready)
+            }
+            (SortState::Start | SortState::ReadyToWork, Some(SortCommands::Load)) => {
+                let ready = matches!(self.state, SortState::ReadyToWork);
+                self.state = SortState::AwaitingLoad { ready };
+                Res::Repeat
+            }
+            (SortState::AwaitingSave, _) => self.handle_save(input),
+            (SortState::ReadyToWork, Some(SortCommands::Save)) => {
+                self.state = SortState::AwaitingSave;
+                Res::Repeat
+            }
+            (SortState::AwaitingAnimation { ready }, _) => {
+                let ready = *ready;
+                self.handle_animation(input, ready)
+            }
+            (SortState::Start | SortState::ReadyToWork, Some(SortCommands::Animate)) => {
+                let ready = matches!(self.state, SortState::ReadyToWork);
+                self.state = SortState::AwaitingAnimation { ready };
+                Res::Repeat
+            }
+            (SortState::AwaitingExternal { ready }, _) => {
+                let ready = *ready;
+                self.handle_external(input, ready)
+            }
+            (SortState::Start | SortState::ReadyToWork, Some(SortCommands::External)) => {
+                let ready = matches!(self.state, SortState::ReadyToWork);
+                self.state = SortState::AwaitingExternal { ready };
+                Res::Repeat
+            }
+            (SortState::Start | SortState::ReadyToWork, Some(SortCommands::Count)) => {
+                self.counting = !self.counting;
+                self.benchmark.counting = self.counting;
+                let record = Record::new("sort", "count").result("counting", self.counting);
+                let format = self.io.format();
+                let counting = self.counting;
+                emit(self.io, format, record, |out| match counting {
+                    true => outln!(out, "Counting comparisons, swaps and writes"),
+                    false => outln!(out, "Not counting operations"),
+                });
+                Res::Repeat
+            }
+            (SortState::Start | SortState::ReadyToWork, Some(SortCommands::Records)) => {
+                self.records = !self.records;
+                // the times of records and integers are not comparable, so statistics start anew
+                let vec = std::mem::take(&mut self.benchmark.vec);
+                self.benchmark = self.new_benchmark(vec);
+                let record = Record::new("sort", "records").result("records", self.records);
+                let format = self.io.format();
+                let records = self.records;
+                emit(self.io, format, record, |out| match records {
+                    true => outln!(out, "Sorting records by descending score"),
+                    false => outln!(out, "Sorting integers"),
+                });
+                Res::Repeat
+            }
+            (SortState::Start, Some(SortCommands::Generate)) => {
+                self.state = SortState::AwaitingArray;
+                self.handle_generate()
+            }
+            (
+                SortState::Start,
+                Some(SortCommands::Sort) | Some(SortCommands::Stat) | Some(SortCommands::Save),
+            ) => {
+                errln!(self.io, "You must create array first");
+                Res::Repeat
+            }
+            (SortState::ReadyToWork, Some(SortCommands::Sort)) => {
+                let format = self.io.format();
+                for _ in 0..self.repetitions {
+                    self.benchmark.work(self.io, format);
+                }
+                Res::Repeat
+            }
+            (SortState::ReadyToWork, Some(SortCommands::Stat)) => {
+                let format = self.io.format();
+                self.benchmark.print_stat(self.io, format);
+                Res::Repeat
+            }
+            _ => {
+                errln!(self.io, "Invalid command");
+                Res::Repeat
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    // generates an array in the sort menu of a session with the seed, like a replay of its recording
+    fn generated(seed: u64) -> Vec<i32> {
+        let script =
+            "generate\n100\n-1000\n1000\nfew-unique:20\nback\ngenerate\n100\n0\n10\n\nexit\n";
+        let mut output = vec![];
+        let mut io = Io::new(Cursor::new(script), &mut output).with_seed(seed);
+        let mut machine = SortStateMachine::new(&mut io);
+        assert!(matches!(machine.start(), Res::Exit));
+        let vec = machine.benchmark.vec.clone();
+        assert!(io.errors().is_empty());
+        vec
+    }
+
+    #[test]
+    fn same_seed_generates_same_arrays() {
+        let first = generated(42);
+        assert_eq!(first.len(), 100);
+        assert!(first.iter().all(|value| (0..10).contains(value)));
+        assert_eq!(generated(42), first);
+        assert_ne!(generated(43), first);
+    }
+}
@@ -0,0 +1,100 @@
+use super::{Algorithm, TracedSortFn};
+use crate::common::outln;
+use crate::report::{emit, Format, Record};
+use course_project::task1::{draw_sort_animation, trace, Step, Traced};
+use std::io::{self, Write};
+
+/// Elements of a random array to animate unless given.
+pub const DEFAULT_SIZE: usize = 16;
+
+/// Most elements of an animated array, the bars get unreadable with more.
+pub const MAX_SIZE: usize = 64;
+
+/// Bounds of the values of a random array to animate unless given.
+pub const DEFAULT_RANGE: (i32, i32) = (1, 100);
+
+/// Seconds every step of the animation takes unless given.
+pub const DEFAULT_STEP_SECONDS: f64 = 0.3;
+
+/// File the animation is saved to unless given.
+pub const DEFAULT_OUTPUT: &str = "sort.svg";
+
+/// A sort whose steps are rendered as animated SVG.
+pub struct Animation {
+    pub name: String,
+    pub title: String,
+    pub sort: TracedSortFn,
+}
+
+impl Animation {
+    /// The sort named `name` among `algorithms`, an error message if it is unknown or its steps
+    /// cannot be traced.
+    pub fn of(algorithms: Vec<Algorithm>, name: &str) -> Result<Self, String> {
+        let algorithm = algorithms
+            .into_iter()
+            .find(|algorithm| algorithm.name == name)
+            .ok_or_else(|| format!("Unknown sort '{}'", name))?;
+        match algorithm.traced {
+            Some(sort) => Ok(Animation {
+                name: algorithm.name,
+                title: algorithm.title,
+                sort,
+            }),
+            None => Err(format!(
+                "The steps of '{}' cannot be traced, only comparison sorts reporting their moves \
+                can be animated",
+                name
+            )),
+        }
+    }
+
+    /// Traces the sort of `vec`, saves the animation to `path` and reports the steps.
+    pub fn run(
+        &self,
+        out: &mut impl Write,
+        format: Format,
+        vec: &[i32],
+        path: &str,
+        step_seconds: f64,
+    ) -> io::Result<()> {
+        let mut data = vec.iter().copied().map(Traced).collect::<Vec<_>>();
+        let steps = trace(&mut data, |slice| (self.sort)(slice));
+        let saved = draw_sort_animation(&self.title, vec, &steps, step_seconds, path);
+        let count = |matches: fn(&Step) -> bool| steps.iter().filter(|step| matches(step)).count();
+        let comparisons = count(|step| matches!(step, Step::Compare(..)));
+        let swaps = count(|step| matches!(step, Step::Swap(..)));
+        let rotations = count(|step| matches!(step, Step::Rotate(..)));
+        let record = Record::new("sort", "animate")
+            .input("sort", self.name.as_str())
+            .input("array", vec)
+            .input("output", path)
+            .input("step_seconds", step_seconds)
+            .result("steps", steps.len())
+            .result("comparisons", comparisons)
+            .result("swaps", swaps)
+            .result("rotations", rotations);
+        let record = match &saved {
+            Ok(()) => record.result("saved", true),
+            Err(err) => record
+                .result("saved", false)
+                .result("error", err.to_string()),
+        };
+        emit(out, format, record, |out| {
+            outln!(
+                out,
+                "{} took {} steps on {} elements: {} comparisons, {} swaps, {} rotations",
+                self.title,
+                steps.len(),
+                vec.len(),
+                comparisons,
+                swaps,
+                rotations
+            );
+            match &saved {
+                Ok(()) => outln!(out, "You can find the animation in {}", path),
+                Err(err) => outln!(out, "Cannot save {}: {}", path, err),
+            }
+        });
+        saved
+    }
+}
@@ -0,0 +1,185 @@
+use super::sweep::SweepResult;
+use super::SortBenchmark;
+use crate::chart::{bar_chart, line_chart, Series};
+use crate::common::outln;
+use crate::report::{emit, Format, Record};
+use crate::statistics::Summary;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+
+// quotes fields containing separators, e.g. the titles of the quicksort variants
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+// missing values are left empty
+fn csv_number(value: f64) -> String {
+    match value.is_nan() {
+        true => String::new(),
+        false => value.to_string(),
+    }
+}
+
+fn write_csv(path: &str, header: &[String], rows: &[Vec<String>]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    let line = |fields: &[String]| {
+        fields
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    writeln!(file, "{}", line(header))?;
+    for row in rows {
+        writeln!(file, "{}", line(row))?;
+    }
+    file.flush()
+}
+
+/// Saves the raw times and the statistics of the benchmark as CSV and the mean times as a bar
+/// chart, returns the paths of the files.
+pub fn save_benchmark(benchmark: &SortBenchmark, prefix: &str) -> io::Result<Vec<String>> {
+    let names = benchmark
+        .algorithms
+        .iter()
+        .map(|algorithm| algorithm.name.clone())
+        .collect::<Vec<String>>();
+    let timings = format!("{}_timings.csv", prefix);
+    let header = ["iteration".to_string()]
+        .into_iter()
+        .chain(names.iter().cloned())
+        .collect::<Vec<String>>();
+    let rows = benchmark
+        .result
+        .iter()
+        .enumerate()
+        .map(|(iteration, times)| {
+            [iteration.to_string()]
+                .into_iter()
+                .chain(times.iter().map(|&time| csv_number(time)))
+                .collect()
+        })
+        .collect::<Vec<Vec<String>>>();
+    write_csv(&timings, &header, &rows)?;
+
+    let summaries = benchmark.summaries();
+    let stability = benchmark.stability();
+    let reference = summaries[0].map_or(f64::NAN, |summary| summary.mean);
+    let summary = format!("{}_summary.csv", prefix);
+    let header = ["sort", "title", "runs"]
+        .into_iter()
+        .chain(Summary::COLUMNS)
+        .chain(["relative_to_std", "stable"])
+        .map(str::to_string)
+        .collect::<Vec<String>>();
+    let rows = benchmark
+        .algorithms
+        .iter()
+        .zip(&summaries)
+        .zip(&stability)
+        .map(|((algorithm, summary), stable)| {
+            let (runs, values, relative) = match summary {
+                Some(summary) => (summary.count, summary.values(), reference / summary.mean),
+                None => (0, [f64::NAN; 11], f64::NAN),
+            };
+            [
+                algorithm.name.clone(),
+                algorithm.title.clone(),
+                runs.to_string(),
+            ]
+            .into_iter()
+            .chain(values.iter().map(|&value| csv_number(value)))
+            .chain([
+                csv_number(relative),
+                stable.map_or(String::new(), |stable| stable.to_string()),
+            ])
+            .collect()
+        })
+        .collect::<Vec<Vec<String>>>();
+    write_csv(&summary, &header, &rows)?;
+
+    let chart = format!("{}_times.svg", prefix);
+    let means = summaries
+        .iter()
+        .map(|summary| summary.map_or(f64::NAN, |summary| summary.mean))
+        .collect::<Vec<f64>>();
+    let title = format!(
+        "Mean time of {} iterations, {} elements",
+        benchmark.iter_number,
+        benchmark.vec.len()
+    );
+    svg::save(&chart, &bar_chart(&title, "s", &names, &means))?;
+    Ok(vec![timings, summary, chart])
+}
+
+/// Saves the times of a sweep as CSV and as a line chart over the array size, returns the paths
+/// of the files.
+pub fn save_sweep(sweep: &SweepResult, prefix: &str) -> io::Result<Vec<String>> {
+    let table = format!("{}_sweep.csv", prefix);
+    let header = ["size".to_string()]
+        .into_iter()
+        .chain(sweep.names.iter().cloned())
+        .collect::<Vec<String>>();
+    let rows = sweep
+        .sizes
+        .iter()
+        .zip(&sweep.times)
+        .map(|(size, times)| {
+            [size.to_string()]
+                .into_iter()
+                .chain(times.iter().map(|&time| csv_number(time)))
+                .collect()
+        })
+        .collect::<Vec<Vec<String>>>();
+    write_csv(&table, &header, &rows)?;
+
+    let chart = format!("{}_sweep.svg", prefix);
+    let series = sweep
+        .names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| Series {
+            name: name.clone(),
+            values: sweep.times.iter().map(|times| times[i]).collect(),
+        })
+        .collect::<Vec<Series>>();
+    let sizes = sweep
+        .sizes
+        .iter()
+        .map(|&size| size as f64)
+        .collect::<Vec<f64>>();
+    let document = line_chart(
+        "Time over array size",
+        "Array size",
+        "Seconds",
+        &sizes,
+        &series,
+    );
+    svg::save(&chart, &document)?;
+    Ok(vec![table, chart])
+}
+
+/// Reports the files written by [`save_benchmark`] or [`save_sweep`] and passes the result on.
+pub fn report_export(
+    out: &mut impl Write,
+    format: Format,
+    prefix: &str,
+    saved: io::Result<Vec<String>>,
+) -> io::Result<Vec<String>> {
+    let record = Record::new("sort", "export").input("prefix", prefix);
+    let record = match &saved {
+        Ok(files) => record.result("saved", true).result("files", files.clone()),
+        Err(err) => record
+            .result("saved", false)
+            .result("error", err.to_string()),
+    };
+    emit(out, format, record, |out| match &saved {
+        Ok(files) => outln!(out, "Saved {}", files.join(", ")),
+        Err(err) => outln!(out, "Cannot export to {}: {}", prefix, err),
+    });
+    saved
+}
@@ -0,0 +1,151 @@
+use super::{format_time, Algorithm};
+use crate::common::outln;
+use crate::report::{emit, Format, Record};
+use course_project::task1::{ExternalSort, ExternalStats, FileFormat};
+use std::cell::Cell;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// Memory budget of the external sort unless given, 64 MiB.
+pub const DEFAULT_MEMORY: Memory = Memory(64 << 20);
+
+/// Sort of the runs unless given.
+pub const DEFAULT_SORT: &str = "intro";
+
+/// A number of bytes, parsed with an optional `K`, `M` or `G` suffix, e.g. `64M`.
+#[derive(Debug, Clone, Copy)]
+pub struct Memory(pub usize);
+
+impl FromStr for Memory {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (digits, shift) = match value.char_indices().last() {
+            Some((i, 'K' | 'k')) => (&value[..i], 10),
+            Some((i, 'M' | 'm')) => (&value[..i], 20),
+            Some((i, 'G' | 'g')) => (&value[..i], 30),
+            _ => (value, 0),
+        };
+        digits
+            .parse::<usize>()
+            .ok()
+            .and_then(|count| count.checked_mul(1 << shift))
+            .map(Memory)
+            .ok_or_else(|| format!("invalid number of bytes '{}'", value))
+    }
+}
+
+/// A file sorted by [`external_sort`].
+pub struct ExternalJob {
+    pub input: String,
+    pub output: String,
+    pub input_format: FileFormat,
+    pub output_format: FileFormat,
+    pub memory: Memory,
+}
+
+impl ExternalJob {
+    /// Sorts `input` into `output` with the formats given by the extensions of the files.
+    pub fn new(input: &str, output: &str, memory: Memory) -> Self {
+        ExternalJob {
+            input: input.to_string(),
+            output: output.to_string(),
+            input_format: FileFormat::of_path(Path::new(input)),
+            output_format: FileFormat::of_path(Path::new(output)),
+            memory,
+        }
+    }
+}
+
+/// Sorts the file of the job with an external merge sort, sorting the runs with `algorithm`, and
+/// reports the runs, merge passes and times. Returns the statistics, or the error after reporting
+/// it.
+///
+/// Runs the algorithm cannot sort, e.g. runs spanning too many values for counting sort, are sorted
+/// with `sort_unstable`.
+pub fn external_sort(
+    out: &mut impl Write,
+    format: Format,
+    job: &ExternalJob,
+    algorithm: Algorithm,
+) -> io::Result<ExternalStats> {
+    let fallbacks = Rc::new(Cell::new(0));
+    let title = algorithm.title.clone();
+    let sorter = ExternalSort::new(job.memory.0)
+        .with_formats(job.input_format, job.output_format)
+        .with_sort({
+            let fallbacks = Rc::clone(&fallbacks);
+            move |run| match (algorithm.applicable)(run) {
+                true => (algorithm.sort)(run),
+                false => {
+                    run.sort_unstable();
+                    fallbacks.set(fallbacks.get() + 1);
+                }
+            }
+        });
+    let sorted = sorter.sort_file(Path::new(&job.input), Path::new(&job.output));
+    let record = Record::new("sort", "external")
+        .input("input", job.input.as_str())
+        .input("output", job.output.as_str())
+        .input("input_format", job.input_format.name())
+        .input("output_format", job.output_format.name())
+        .input("memory", sorter.memory())
+        .input("sort", title.as_str());
+    let record = match &sorted {
+        Ok(stats) => record
+            .result("sorted", true)
+            .result("values", stats.values)
+            .result("runs", stats.runs)
+            .result("run_length", sorter.run_length())
+            .result("fan_in", sorter.fan_in())
+            .result("passes", stats.passes)
+            .result("fallback_runs", fallbacks.get())
+            .timing("runs", stats.run_time.as_secs_f64())
+            .timing("merge", stats.merge_time.as_secs_f64()),
+        Err(err) => record
+            .result("sorted", false)
+            .result("error", err.to_string()),
+    };
+    emit(out, format, record, |out| match &sorted {
+        Ok(stats) => {
+            outln!(
+                out,
+                "Sorted {} values of {} into {}: {} runs of up to {} values sorted by {}, {} \
+                merge passes of up to {} runs",
+                stats.values,
+                job.input,
+                job.output,
+                stats.runs,
+                sorter.run_length(),
+                title,
+                stats.passes,
+                sorter.fan_in()
+            );
+            outln!(
+                out,
+                "Runs: {} s, merge: {} s",
+                format_time(stats.run_time.as_secs_f64()),
+                format_time(stats.merge_time.as_secs_f64())
+            );
+            if fallbacks.get() > 0 {
+                outln!(
+                    out,
+                    "{} runs could not be sorted with {} and were sorted with sort_unstable",
+                    fallbacks.get(),
+                    title
+                );
+            }
+        }
+        Err(err) => outln!(
+            out,
+            "Cannot sort {} into {}: {}",
+            job.input,
+            job.output,
+            err
+        ),
+    });
+    sorted
+}
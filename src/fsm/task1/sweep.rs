@@ -0,0 +1,260 @@
+use super::{format_time, Algorithm};
+use crate::common::outln;
+use crate::distribution::Distribution;
+use crate::report::{emit, Format, Json, Record};
+use rand::Rng;
+use std::io::Write;
+use std::time::Instant;
+
+/// Smallest and largest array size of a sweep unless given.
+pub const DEFAULT_SIZES: (usize, usize) = (10, 1_000_000);
+
+/// Seconds a single run may take before a sort is skipped for the larger sizes.
+pub const DEFAULT_BUDGET: f64 = 1.0;
+
+/// Sizes per factor of 10.
+pub const DEFAULT_STEPS: usize = 1;
+
+/// Most sizes per factor of 10.
+pub const MAX_STEPS: usize = 100;
+
+/// Largest array size of a sweep.
+pub const MAX_SIZE: usize = 100_000_000;
+
+/// Most sizes of a sweep.
+pub const MAX_SIZES: usize = 1000;
+
+/// Runs per size unless given.
+pub const DEFAULT_RUNS: usize = 3;
+
+/// Bounds of the values unless given.
+pub const DEFAULT_RANGE: (i32, i32) = (0, 1_000_000);
+
+/// Runs every sort on arrays of geometrically growing size and fits the growth of the running time.
+pub struct Sweep {
+    pub sizes: Vec<usize>,
+    pub left: i32,
+    pub right: i32,
+    pub distribution: Distribution,
+    pub budget: f64,
+    /// Runs per size, the median time is taken.
+    pub runs: usize,
+}
+
+/// Times measured by [`Sweep::run`].
+pub struct SweepResult {
+    pub sizes: Vec<usize>,
+    /// Names of the sorts.
+    pub names: Vec<String>,
+    /// Median seconds per size and sort, NaN if skipped.
+    pub times: Vec<Vec<f64>>,
+}
+
+/// Sizes from `from` to `to` inclusive, `steps` per factor of 10 but at most [`MAX_STEPS`].
+///
+/// Returns at most [`MAX_SIZES`] sizes, the smallest ones.
+pub fn geometric_sizes(from: usize, to: usize, steps: usize) -> Vec<usize> {
+    let from = from.max(1) as f64;
+    let steps = steps.clamp(1, MAX_STEPS) as f64;
+    let mut sizes = vec![];
+    for i in 0..MAX_SIZES {
+        let size = (from * 10f64.powf(i as f64 / steps)).round();
+        if size > to as f64 {
+            break;
+        }
+        let size = size as usize;
+        if sizes.last() != Some(&size) {
+            sizes.push(size);
+        }
+    }
+    sizes
+}
+
+/// Exponent `b` of the least squares fit `time = a * size^b` on the log-log scale, `None` with
+/// fewer than two sizes.
+pub fn growth_exponent(points: &[(usize, f64)]) -> Option<f64> {
+    let points = points
+        .iter()
+        .filter(|(_, time)| *time > 0.0)
+        .map(|&(size, time)| ((size as f64).ln(), time.ln()))
+        .collect::<Vec<(f64, f64)>>();
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let covariance = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+    (points.len() >= 2 && variance > 0.0).then(|| covariance / variance)
+}
+
+impl Sweep {
+    /// Measures every size and prints a row of times per size, then the growth exponents.
+    ///
+    /// A sort is skipped for the remaining sizes once a run took longer than the budget or the
+    /// next run is expected to.
+    pub fn run(
+        &self,
+        out: &mut impl Write,
+        format: Format,
+        algorithms: &[Algorithm],
+        rng: &mut impl Rng,
+    ) -> SweepResult {
+        let names = algorithms
+            .iter()
+            .map(|algorithm| algorithm.name.as_str())
+            .collect::<Vec<&str>>();
+        if format == Format::Text {
+            outln!(out, "Size, {}", names.join(", "));
+        }
+        let mut points = vec![vec![]; algorithms.len()];
+        let mut skipped = vec![false; algorithms.len()];
+        let mut rows = vec![];
+
+        for &size in &self.sizes {
+            let vec = self.distribution.generate(rng, size, self.left, self.right);
+            let mut expected: Option<Vec<i32>> = None;
+            let mut times = vec![];
+            for (i, algorithm) in algorithms.iter().enumerate() {
+                if !skipped[i] {
+                    skipped[i] = expected_time(&points[i], size) > self.budget;
+                }
+                if skipped[i] || !(algorithm.applicable)(&vec) {
+                    times.push(f64::NAN);
+                    continue;
+                }
+                let mut runs = vec![];
+                for _ in 0..self.runs.max(1) {
+                    let mut data = vec.clone();
+                    let now = Instant::now();
+                    (algorithm.sort)(&mut data);
+                    runs.push(now.elapsed().as_secs_f64());
+                    match &expected {
+                        Some(expected) => {
+                            assert_eq!(&data, expected, "{} failed", algorithm.title)
+                        }
+                        None => expected = Some(data),
+                    }
+                }
+                runs.sort_unstable_by(f64::total_cmp);
+                let time = runs[runs.len() / 2];
+                points[i].push((size, time));
+                skipped[i] = time > self.budget;
+                times.push(time);
+            }
+
+            let record = algorithms.iter().zip(&times).fold(
+                Record::new("sort", "sweep")
+                    .input("size", size)
+                    .input("distribution", self.distribution.to_string()),
+                |record, (algorithm, time)| record.timing(&algorithm.name, *time),
+            );
+            emit(out, format, record, |out| {
+                let times = times
+                    .iter()
+                    .map(|&time| format_time(time))
+                    .collect::<Vec<String>>();
+                outln!(out, "{}, {}", size, times.join(", "));
+            });
+            rows.push(times);
+        }
+
+        let exponents = points
+            .iter()
+            .map(|points| growth_exponent(points))
+            .collect::<Vec<Option<f64>>>();
+        let record = Record::new("sort", "sweep_fit")
+            .input("sizes", self.sizes.clone())
+            .input("budget", self.budget)
+            .result(
+                "growth_exponents",
+                Json::Object(
+                    names
+                        .iter()
+                        .zip(&exponents)
+                        .map(|(name, &exponent)| (name.to_string(), exponent.into()))
+                        .collect(),
+                ),
+            );
+        emit(out, format, record, |out| {
+            let exponents = algorithms
+                .iter()
+                .zip(&exponents)
+                .map(|(algorithm, exponent)| match exponent {
+                    Some(exponent) => format!("{}: n^{:.2}", algorithm.title, exponent),
+                    None => format!("{}: -", algorithm.title),
+                })
+                .collect::<Vec<String>>();
+            outln!(out, "Empirical growth: {}", exponents.join(", "));
+        });
+        SweepResult {
+            sizes: self.sizes.clone(),
+            names: names.iter().map(|name| name.to_string()).collect(),
+            times: rows,
+        }
+    }
+}
+
+// time of the next run extrapolated from the last two measurements, at least linear growth
+fn expected_time(points: &[(usize, f64)], size: usize) -> f64 {
+    match points {
+        [] => 0.0,
+        [.., (last_size, last_time)] => {
+            let exponent = match points {
+                [.., previous, _] => growth_exponent(&[*previous, (*last_size, *last_time)]),
+                _ => None,
+            };
+            last_time * (size as f64 / *last_size as f64).powf(exponent.unwrap_or(1.0).max(1.0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_grow_geometrically() {
+        assert_eq!(geometric_sizes(10, 1000, 1), [10, 100, 1000]);
+        assert_eq!(geometric_sizes(10, 999, 1), [10, 100]);
+        assert_eq!(geometric_sizes(1, 10, 2), [1, 3, 10]);
+        assert_eq!(geometric_sizes(1, 10, 4), [1, 2, 3, 6, 10]);
+        assert_eq!(geometric_sizes(1, 3, 10), [1, 2, 3]);
+        assert_eq!(geometric_sizes(5, 5, 1), [5]);
+        assert_eq!(geometric_sizes(0, 10, 0), [1, 10]);
+        assert_eq!(geometric_sizes(100, 10, 1), []);
+    }
+
+    #[test]
+    fn sizes_end_on_extreme_bounds() {
+        let sizes = geometric_sizes(1, usize::MAX, 1);
+        assert_eq!(sizes.len(), 20);
+        assert_eq!(sizes.last(), Some(&10_000_000_000_000_000_000));
+        let sizes = geometric_sizes(1, usize::MAX, usize::MAX);
+        assert!(sizes.len() <= MAX_SIZES);
+        assert!(sizes.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(geometric_sizes(usize::MAX, usize::MAX, 1), [usize::MAX]);
+    }
+
+    #[test]
+    fn growth_exponent_fits_power_laws() {
+        let quadratic = [(10, 2e-4), (100, 2e-2), (1000, 2.0)];
+        assert!((growth_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+        let linear = [(1, 3.0), (2, 6.0), (4, 12.0), (8, 24.0)];
+        assert!((growth_exponent(&linear).unwrap() - 1.0).abs() < 1e-9);
+        let constant = [(1, 5.0), (1000, 5.0)];
+        assert!(growth_exponent(&constant).unwrap().abs() < 1e-9);
+    }
+
+    #[test]
+    fn growth_exponent_needs_two_sizes() {
+        assert_eq!(growth_exponent(&[]), None);
+        assert_eq!(growth_exponent(&[(10, 1.0)]), None);
+        assert_eq!(growth_exponent(&[(10, 1.0), (10, 2.0)]), None);
+        assert_eq!(growth_exponent(&[(10, 1.0), (100, 0.0)]), None);
+    }
+}
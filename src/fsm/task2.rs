@@ -0,0 +1,614 @@
+use crate::common::{errln, outln, ArrayGenerationInputStateMachine, Io, LineReader, Res};
+use crate::dataset;
+use crate::machine::{commands, StateMachine};
+use crate::report::{emit, Format, Record};
+use course_project::task2::fibonacci::{delete, fib_search, insert};
+use course_project::task2::hash::CustomHasSet;
+use std::io::Write;
+use std::time::Instant;
+
+#[derive(Debug)]
+enum Task2State {
+    AwaitingCommand,
+}
+
+commands! {
+    pub enum Task2Commands {
+        Finder => ("finder", "Fibonacci search implementation"),
+        Hasher => ("hasher", "Simple rehash implementation"),
+    }
+}
+
+pub struct Task2StateMachine<'a, R: LineReader, W: Write> {
+    state: Task2State,
+    io: &'a mut Io<R, W>,
+}
+
+impl<'a, R: LineReader, W: Write> Task2StateMachine<'a, R, W> {
+    pub fn new(io: &'a mut Io<R, W>) -> Self {
+        Task2StateMachine {
+            state: Task2State::AwaitingCommand,
+            io,
+        }
+    }
+}
+
+impl<'a, R: LineReader, W: Write> StateMachine<R, W> for Task2StateMachine<'a, R, W> {
+    type Command = Task2Commands;
+    type Output = ();
+
+    fn io(&mut self) -> &mut Io<R, W> {
+        self.io
+    }
+
+    fn commands(&self) -> Vec<Task2Commands> {
+        match self.state {
+            Task2State::AwaitingCommand => vec![Task2Commands::Finder, Task2Commands::Hasher],
+        }
+    }
+
+    fn print(&mut self) {
+        match self.state {
+            Task2State::AwaitingCommand => {
+                let commands = self.commands_text();
+                outln!(self.io, "Available commands:\n{}", commands);
+            }
+        }
+    }
+
+    fn handle_input(&mut self, _input: &str, command: Option<Task2Commands>) -> Res<()> {
+        match (&self.state, command) {
+            (Task2State::AwaitingCommand, Some(Task2Commands::Finder)) => {
+                FibStateMachine::new(self.io).start().nested(Res::Result)
+            }
+            (Task2State::AwaitingCommand, Some(Task2Commands::Hasher)) => {
+                HashStateMachine::new(self.io).start().nested(Res::Result)
+            }
+            _ => {
+                errln!(self.io, "Invalid command");
+                Res::Repeat
+            }
+        }
+    }
+}
+
+pub fn run_search(out: &mut impl Write, format: Format, vec: &[i32], num: i32) {
+    let mut now: Instant;
+
+    now = Instant::now();
+    let (fib_res, _) = fib_search(vec, &num);
+    let fib_duration = now.elapsed().as_secs_f64();
+
+    now = Instant::now();
+    let binary_res = vec.binary_search(&num).ok();
+    let binary_duration = now.elapsed().as_secs_f64();
+
+    let record = Record::new("fibsearch", "search")
+        .input("size", vec.len())
+        .input("value", num)
+        .result("fibonacci", fib_res)
+        .result("binary", binary_res)
+        .timing("fibonacci", fib_duration)
+        .timing("binary", binary_duration);
+    emit(out, format, record, |out| {
+        outln!(out, "Fibonacci search took {:.10}", fib_duration);
+        match fib_res {
+            Some(idx) => outln!(out, "Fibonacci: Element {} found at index {}", num, idx),
+            None => outln!(out, "Fibonacci: Element {} not found", num),
+        };
+        outln!(out, "Binary search took {:.10}", binary_duration);
+        match binary_res {
+            Some(idx) => {
+                outln!(
+                    out,
+                    "Std binary search: Element {} found at index {}",
+                    num,
+                    idx
+                )
+            }
+            None => outln!(out, "Std binary search: Element {} not found", num),
+        };
+    });
+}
+
+pub fn run_create(out: &mut impl Write, format: Format, size: usize) -> CustomHasSet {
+    let record = Record::new("hashset", "create").input("size", size);
+    emit(out, format, record, |out| {
+        outln!(out, "Set with size {} created", size)
+    });
+    CustomHasSet::new(size)
+}
+
+pub fn run_add(out: &mut impl Write, format: Format, set: &mut CustomHasSet, num: i32) {
+    let res = set.add(num);
+    let status = match res {
+        Ok(true) => "added",
+        Ok(false) => "exists",
+        Err(_) => "full",
+    };
+    let record = Record::new("hashset", "add")
+        .input("value", num)
+        .result("status", status);
+    emit(out, format, record, |out| {
+        match res {
+            Ok(true) => outln!(out, "Element {} added", num),
+            Ok(false) => outln!(out, "Element {} already exists", num),
+            Err(_) => outln!(out, "Set is full"),
+        };
+    });
+}
+
+pub fn run_contains(out: &mut impl Write, format: Format, set: &CustomHasSet, num: i32) {
+    let found = set.contains(num);
+    let record = Record::new("hashset", "search")
+        .input("value", num)
+        .result("found", found);
+    emit(out, format, record, |out| {
+        match found {
+            true => outln!(out, "Element {} found", num),
+            false => outln!(out, "Element {} not found", num),
+        };
+    });
+}
+
+#[derive(Debug)]
+enum FibState {
+    Start,
+    ReadyToWork,
+    AwaitingItemToSearch,
+    AwaitingItemToInsert,
+    AwaitingItemToDelete,
+    AwaitingFileToLoad,
+    AwaitingFileToSave,
+}
+
+commands! {
+    pub enum FibCommands {
+        Generate => ("generate", "Generate array"),
+        Load => ("load", "Load array from a file"),
+        Search => ("search", "Search element"),
+        Insert => ("insert", "Insert element"),
+        Delete => ("delete", "Delete element"),
+        Print => ("print", "Print array"),
+        Save => ("save", "Save array to a file"),
+    }
+}
+
+struct FibStateMachine<'a, R: LineReader, W: Write> {
+    state: FibState,
+    io: &'a mut Io<R, W>,
+    vec: Vec<i32>,
+}
+
+impl<'a, R: LineReader, W: Write> FibStateMachine<'a, R, W> {
+    pub fn new(io: &'a mut Io<R, W>) -> Self {
+        FibStateMachine {
+            state: FibState::Start,
+            io,
+            vec: vec![],
+        }
+    }
+
+    fn handle_delete(&mut self, input: &str) -> Res<()> {
+        match self.parse_item(input) {
+            Some(num) => {
+                let res = delete(&mut self.vec, &num);
+                let record = Record::new("fibsearch", "delete")
+                    .input("value", num)
+                    .result("index", res);
+                let format = self.io.format();
+                emit(self.io, format, record, |out| {
+                    match res {
+                        Some(idx) => outln!(out, "Element {} deleted from position {}", num, idx),
+                        None => outln!(out, "Element {} not found", num),
+                    };
+                });
+                self.state = FibState::ReadyToWork;
+                Res::Repeat
+            }
+            None => {
+                errln!(
+                    self.io,
+                    "Invalid item for delete. Item must be a number between {} and {}",
+                    i32::MIN,
+                    i32::MAX
+                );
+                Res::Repeat
+            }
+        }
+    }
+
+    fn handle_insert(&mut self, input: &str) -> Res<()> {
+        match self.parse_item(input) {
+            Some(num) => {
+                let idx = insert(&mut self.vec, num);
+                self.state = FibState::ReadyToWork;
+                let record = Record::new("fibsearch", "insert")
+                    .input("value", num)
+                    .result("index", idx);
+                let format = self.io.format();
+                emit(self.io, format, record, |out| {
+                    outln!(out, "Element {} inserted in position {}", num, idx)
+                });
+                Res::Repeat
+            }
+            None => {
+                errln!(
+                    self.io,
+                    "Invalid item for insert. Item must be a number between {} and {}",
+                    i32::MIN,
+                    i32::MAX
+                );
+                Res::Repeat
+            }
+        }
+    }
+
+    fn handle_search(&mut self, input: &str) -> Res<()> {
+        match self.parse_item(input) {
+            Some(num) => {
+                let format = self.io.format();
+                run_search(self.io, format, &self.vec, num);
+                self.state = FibState::ReadyToWork;
+                Res::Repeat
+            }
+            None => {
+                errln!(
+                    self.io,
+                    "Invalid item for search. Item must be a number between {} and {}",
+                    i32::MIN,
+                    i32::MAX
+                );
+                Res::Repeat
+            }
+        }
+    }
+
+    fn handle_load(&mut self, input: &str) -> Res<()> {
+        let path = input.trim();
+        if path.is_empty() || path == dataset::STDIN {
+            errln!(
+                self.io,
+                "Enter the path of a file, the input holds the menu commands"
+            );
+            return Res::Repeat;
+        }
+        let format = self.io.format();
+        self.state = match dataset::report_load(self.io, format, "fibsearch", path) {
+            Some(mut vec) => {
+                vec.sort_unstable();
+                self.vec = vec;
+                FibState::ReadyToWork
+            }
+            None => FibState::Start,
+        };
+        Res::Repeat
+    }
+
+    fn handle_save(&mut self, input: &str) -> Res<()> {
+        let path = input.trim();
+        if path.is_empty() {
+            errln!(self.io, "File path must not be empty");
+            return Res::Repeat;
+        }
+        let format = self.io.format();
+        dataset::report_save(self.io, format, "fibsearch", path, &self.vec);
+        self.state = FibState::ReadyToWork;
+        Res::Repeat
+    }
+
+    fn parse_item(&self, input: &str) -> Option<i32> {
+        input.trim().parse::<i32>().ok()
+    }
+
+    fn handle_work(&mut self, command: FibCommands) -> Res<()> {
+        let text = self.io.format() == Format::Text;
+        match command {
+            FibCommands::Search => {
+                if text {
+                    outln!(self.io, "Input number to search or 'main' to return to main menu, or 'exit' to exit program:");
+                }
+                self.state = FibState::AwaitingItemToSearch;
+            }
+            FibCommands::Insert => {
+                if text {
+                    outln!(self.io, "Input number to insert or 'main' to return to main menu, or 'exit' to exit program:");
+                }
+                self.state = FibState::AwaitingItemToInsert;
+            }
+            FibCommands::Delete => {
+                if text {
+                    outln!(self.io, "Input number to delete or 'main' to return to main menu, or 'exit' to exit program:");
+                }
+                self.state = FibState::AwaitingItemToDelete;
+            }
+            FibCommands::Save => {
+                if text {
+                    outln!(self.io, "Input file to save the array to, binary if it ends with '.bin', or 'main' to return to main menu, or 'exit' to exit program:");
+                }
+                self.state = FibState::AwaitingFileToSave;
+            }
+            _ => {
+                errln!(self.io, "Invalid command")
+            }
+        }
+        Res::Repeat
+    }
+
+    fn handle_start(&mut self, command: FibCommands) -> Res<()> {
+        match command {
+            FibCommands::Generate => self.handle_generation(),
+            FibCommands::Load => {
+                if self.io.format() == Format::Text {
+                    outln!(self.io, "Input file to load the array from, binary if it ends with '.bin', numbers separated by whitespace, commas or line breaks otherwise, or 'main' to return to main menu, or 'exit' to exit program:");
+                }
+                self.state = FibState::AwaitingFileToLoad;
+                Res::Repeat
+            }
+            FibCommands::Insert | FibCommands::Search | FibCommands::Delete | FibCommands::Save => {
+                errln!(self.io, "You must generate or load array first");
+                Res::Repeat
+            }
+            _ => {
+                errln!(self.io, "Invalid command");
+                Res::Repeat
+            }
+        }
+    }
+
+    fn handle_generation(&mut self) -> Res<()> {
+        ArrayGenerationInputStateMachine::new(self.io)
+            .start()
+            .nested(|mut vec| {
+                vec.sort_unstable();
+                self.vec = vec;
+                self.state = FibState::ReadyToWork;
+                Res::Repeat
+            })
+    }
+}
+
+impl<'a, R: LineReader, W: Write> StateMachine<R, W> for FibStateMachine<'a, R, W> {
+    type Command = FibCommands;
+    type Output = ();
+
+    fn io(&mut self) -> &mut Io<R, W> {
+        self.io
+    }
+
+    fn commands(&self) -> Vec<FibCommands> {
+        match self.state {
+            FibState::Start => vec![FibCommands::Generate, FibCommands::Load],
+            FibState::ReadyToWork => vec![
+                FibCommands::Search,
+                FibCommands::Insert,
+                FibCommands::Delete,
+                FibCommands::Print,
+                FibCommands::Save,
+            ],
+            FibState::AwaitingItemToSearch
+            | FibState::AwaitingItemToInsert
+            | FibState::AwaitingItemToDelete
+            | FibState::AwaitingFileToLoad
+            | FibState::AwaitingFileToSave => vec![],
+        }
+    }
+
+    fn back(&mut self) -> Res<()> {
+        match self.state {
+            FibState::Start => return Res::Back,
+            FibState::ReadyToWork | FibState::AwaitingFileToLoad => self.state = FibState::Start,
+            FibState::AwaitingItemToSearch
+            | FibState::AwaitingItemToInsert
+            | FibState::AwaitingItemToDelete
+            | FibState::AwaitingFileToSave => self.state = FibState::ReadyToWork,
+        }
+        Res::Repeat
+    }
+
+    fn print(&mut self) {
+        match self.state {
+            FibState::Start => {
+                let commands = self.commands_text();
+                outln!(self.io, "Available commands:\n{}", commands)
+            }
+            FibState::ReadyToWork => {
+                outln!(self.io, "Array with size {} is ready.", self.vec.len(),);
+                let commands = self.commands_text();
+                outln!(self.io, "Available commands:\n{}", commands);
+            }
+            _ => (),
+        }
+    }
+
+    fn handle_input(&mut self, input: &str, command: Option<FibCommands>) -> Res<()> {
+        match (&self.state, command) {
+            (FibState::ReadyToWork, Some(FibCommands::Print)) => {
+                let record = Record::new("fibsearch", "print").result("array", self.vec.as_slice());
+                let format = self.io.format();
+                emit(self.io, format, record, |out| outln!(out, "{:?}", self.vec));
+                Res::Repeat
+            }
+            (FibState::Start, Some(command)) => self.handle_start(command),
+            (FibState::ReadyToWork, Some(command)) => self.handle_work(command),
+            (FibState::AwaitingItemToSearch, _) => self.handle_search(input),
+            (FibState::AwaitingItemToInsert, _) => self.handle_insert(input),
+            (FibState::AwaitingItemToDelete, _) => self.handle_delete(input),
+            (FibState::AwaitingFileToLoad, _) => self.handle_load(input),
+            (FibState::AwaitingFileToSave, _) => self.handle_save(input),
+            _ => {
+                errln!(self.io, "Invalid input");
+                Res::Repeat
+            }
+        }
+    }
+}
+
+commands! {
+    pub enum HashCommands {
+        Add => ("add", "Add an element to the set"),
+        Contains => ("search", "Search an element in the set"),
+    }
+}
+
+#[derive(Debug)]
+enum HashState {
+    Start,
+    ReadyToWork,
+    AwaitingItemToAdd,
+    AwaitingItemToSearch,
+}
+
+struct HashStateMachine<'a, R: LineReader, W: Write> {
+    state: HashState,
+    io: &'a mut Io<R, W>,
+    set: CustomHasSet,
+}
+
+impl<'a, R: LineReader, W: Write> HashStateMachine<'a, R, W> {
+    pub fn new(io: &'a mut Io<R, W>) -> Self {
+        HashStateMachine {
+            state: HashState::Start,
+            io,
+            set: CustomHasSet::new(0),
+        }
+    }
+
+    fn handle_start(&mut self, input: &str) -> Res<()> {
+        match input.trim().parse::<usize>() {
+            Ok(size) => {
+                let format = self.io.format();
+                self.set = run_create(self.io, format, size);
+                self.state = HashState::ReadyToWork;
+            }
+            Err(_) => {
+                errln!(
+                    self.io,
+                    "Invalid size. Size must be a number between {} and {}",
+                    usize::MIN,
+                    usize::MAX
+                );
+            }
+        };
+        Res::Repeat
+    }
+
+    fn handle_work(&mut self, command: HashCommands) -> Res<()> {
+        match command {
+            HashCommands::Add => {
+                self.state = HashState::AwaitingItemToAdd;
+            }
+            HashCommands::Contains => {
+                self.state = HashState::AwaitingItemToSearch;
+            }
+        }
+        Res::Repeat
+    }
+
+    fn handle_add(&mut self, input: &str) -> Res<()> {
+        match input.trim().parse::<i32>() {
+            Ok(num) => {
+                let format = self.io.format();
+                run_add(self.io, format, &mut self.set, num);
+                self.state = HashState::ReadyToWork;
+            }
+            Err(_) => {
+                errln!(
+                    self.io,
+                    "Invalid item for add. Item must be a number between {} and {}",
+                    i32::MIN,
+                    i32::MAX
+                );
+            }
+        };
+        Res::Repeat
+    }
+
+    fn handle_search(&mut self, input: &str) -> Res<()> {
+        match input.trim().parse::<i32>() {
+            Ok(num) => {
+                let format = self.io.format();
+                run_contains(self.io, format, &self.set, num);
+                self.state = HashState::ReadyToWork;
+            }
+            Err(_) => {
+                errln!(
+                    self.io,
+                    "Invalid item for search. Item must be a number between {} and {}",
+                    i32::MIN,
+                    i32::MAX
+                );
+            }
+        };
+        Res::Repeat
+    }
+}
+
+impl<'a, R: LineReader, W: Write> StateMachine<R, W> for HashStateMachine<'a, R, W> {
+    type Command = HashCommands;
+    type Output = ();
+
+    fn io(&mut self) -> &mut Io<R, W> {
+        self.io
+    }
+
+    fn commands(&self) -> Vec<HashCommands> {
+        match self.state {
+            HashState::ReadyToWork => vec![HashCommands::Add, HashCommands::Contains],
+            HashState::Start | HashState::AwaitingItemToAdd | HashState::AwaitingItemToSearch => {
+                vec![]
+            }
+        }
+    }
+
+    fn back(&mut self) -> Res<()> {
+        match self.state {
+            HashState::Start => return Res::Back,
+            HashState::ReadyToWork => self.state = HashState::Start,
+            HashState::AwaitingItemToAdd | HashState::AwaitingItemToSearch => {
+                self.state = HashState::ReadyToWork
+            }
+        }
+        Res::Repeat
+    }
+
+    fn print(&mut self) {
+        let commands = self.commands_text();
+        match self.state {
+            HashState::Start => outln!(
+                self.io,
+                "Enter size of the set or one of available commands:\n{}:",
+                commands,
+            ),
+            HashState::ReadyToWork => outln!(
+                self.io,
+                "Set with size {} is ready.\nAvailable commands:\n{}",
+                self.set.get_size(),
+                commands,
+            ),
+            HashState::AwaitingItemToAdd => outln!(
+                self.io,
+                "Enter number to add one of available commands:\n{}:",
+                commands
+            ),
+            HashState::AwaitingItemToSearch => outln!(
+                self.io,
+                "Enter number to search one of available commands:\n{}:",
+                commands
+            ),
+        }
+    }
+
+    fn handle_input(&mut self, input: &str, command: Option<HashCommands>) -> Res<()> {
+        match (&self.state, command) {
+            (HashState::Start, _) => self.handle_start(input),
+            (HashState::ReadyToWork, Some(command)) => self.handle_work(command),
+            (HashState::AwaitingItemToAdd, _) => self.handle_add(input),
+            (HashState::AwaitingItemToSearch, _) => self.handle_search(input),
+            _ => {
+                errln!(self.io, "Invalid input");
+                Res::Repeat
+            }
+        }
+    }
+}
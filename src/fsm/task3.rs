@@ -0,0 +1,200 @@
+use crate::common::{errln, outln, Io, LineReader, Res};
+use crate::machine::{commands, Command, GlobalCommands, StateMachine};
+use crate::report::{emit, Format, Record};
+
+use course_project::task3::substr_search::boyer_moor_search;
+use std::io::Write;
+use std::time::Instant;
+
+pub fn run_search(
+    out: &mut impl Write,
+    format: Format,
+    string: &str,
+    pattern: &str,
+    case_insensitive: bool,
+) -> Vec<usize> {
+    let mut now = Instant::now();
+    let result = boyer_moor_search(string, pattern, !case_insensitive);
+    let sbm_duration = now.elapsed();
+
+    now = Instant::now();
+    let std_result = if case_insensitive {
+        string
+            .to_lowercase()
+            .match_indices(&pattern.to_lowercase())
+            .map(|(i, _)| string[..i].chars().count())
+            .collect::<Vec<_>>()
+    } else {
+        string
+            .match_indices(pattern)
+            .map(|(i, _)| string[..i].chars().count())
+            .collect::<Vec<_>>()
+    };
+    let std_duration = now.elapsed();
+
+    let record = Record::new("search", "search")
+        .input("string", string)
+        .input("pattern", pattern)
+        .input("case_insensitive", case_insensitive)
+        .result("sbm", result.as_slice())
+        .result("std", std_result.as_slice())
+        .timing("sbm", sbm_duration.as_secs_f64())
+        .timing("std", std_duration.as_secs_f64());
+    emit(out, format, record, |out| {
+        outln!(
+            out,
+            "SBM: Pattern found at positions: {:?}. Microseconds elapsed: {}",
+            result,
+            sbm_duration.as_micros()
+        );
+        outln!(
+            out,
+            "STD: Pattern found at positions: {:?}. Microseconds elapsed: {}",
+            std_result,
+            std_duration.as_micros()
+        );
+    });
+    result
+}
+
+#[derive(Debug)]
+enum FinderState {
+    Start,
+    AwaitingSting,
+    AwaitingPattern,
+    AwaitingCommand,
+}
+
+commands! {
+    pub enum FinderCommands {
+        EnterString => ("string", "Enter string"),
+        EnterPattern => ("pattern", "Enter pattern"),
+        Search => ("search", "Search"),
+        SearchCaseInsensitive => ("searchi", "Search case insensitive"),
+    }
+}
+
+pub struct SimplifiedBoyerMoorStateMachine<'a, R: LineReader, W: Write> {
+    state: FinderState,
+    io: &'a mut Io<R, W>,
+    string: String,
+    pattern: String,
+}
+
+impl<'a, R: LineReader, W: Write> SimplifiedBoyerMoorStateMachine<'a, R, W> {
+    pub fn new(io: &'a mut Io<R, W>) -> Self {
+        SimplifiedBoyerMoorStateMachine {
+            state: FinderState::Start,
+            io,
+            string: String::default(),
+            pattern: String::default(),
+        }
+    }
+
+    fn search(&mut self, case_insensitive: bool) -> Vec<usize> {
+        let format = self.io.format();
+        run_search(
+            self.io,
+            format,
+            &self.string,
+            &self.pattern,
+            case_insensitive,
+        )
+    }
+
+    fn handle_pattern(&mut self, input: &str) -> Res<()> {
+        self.pattern = self.parse_string(input);
+        self.state = FinderState::AwaitingCommand;
+        Res::Repeat
+    }
+
+    fn handle_string(&mut self, input: &str) -> Res<()> {
+        self.string = self.parse_string(input);
+        self.state = FinderState::AwaitingPattern;
+        Res::Repeat
+    }
+
+    fn parse_string(&self, input: &str) -> String {
+        input.trim().to_string()
+    }
+}
+
+impl<'a, R: LineReader, W: Write> StateMachine<R, W> for SimplifiedBoyerMoorStateMachine<'a, R, W> {
+    type Command = FinderCommands;
+    type Output = ();
+
+    fn io(&mut self) -> &mut Io<R, W> {
+        self.io
+    }
+
+    fn commands(&self) -> Vec<FinderCommands> {
+        match self.state {
+            FinderState::Start => vec![FinderCommands::EnterString],
+            FinderState::AwaitingSting | FinderState::AwaitingPattern => vec![],
+            FinderState::AwaitingCommand => vec![
+                FinderCommands::EnterString,
+                FinderCommands::EnterPattern,
+                FinderCommands::Search,
+                FinderCommands::SearchCaseInsensitive,
+            ],
+        }
+    }
+
+    fn global_commands(&self) -> &'static [GlobalCommands] {
+        match self.state {
+            FinderState::Start | FinderState::AwaitingCommand => GlobalCommands::ALL,
+            // the string and the pattern may be any text, even a command
+            FinderState::AwaitingSting | FinderState::AwaitingPattern => &[],
+        }
+    }
+
+    fn print(&mut self) {
+        let commands = self.commands_text();
+        match self.state {
+            FinderState::Start => {
+                outln!(self.io, "Available commands:\n{}", commands);
+            }
+            FinderState::AwaitingSting => {
+                outln!(self.io, "Enter string for searching in");
+            }
+            FinderState::AwaitingPattern => {
+                outln!(self.io, "Enter pattern to search for");
+            }
+            FinderState::AwaitingCommand => {
+                outln!(self.io, "String: {}", self.string);
+                outln!(self.io, "Pattern: {}", self.pattern);
+                outln!(self.io, "Available commands:\n{}", commands);
+            }
+        }
+    }
+
+    fn handle_input(&mut self, input: &str, command: Option<FinderCommands>) -> Res<()> {
+        match (&self.state, command) {
+            (FinderState::AwaitingSting, _) => self.handle_string(input),
+            (FinderState::AwaitingPattern, _) => self.handle_pattern(input),
+            (
+                FinderState::Start | FinderState::AwaitingCommand,
+                Some(FinderCommands::EnterString),
+            ) => {
+                self.state = FinderState::AwaitingSting;
+                Res::Repeat
+            }
+            (FinderState::AwaitingCommand, Some(FinderCommands::EnterPattern)) => {
+                self.state = FinderState::AwaitingPattern;
+                Res::Repeat
+            }
+            (FinderState::AwaitingCommand, Some(FinderCommands::Search)) => {
+                self.search(false);
+                Res::Repeat
+            }
+            (FinderState::AwaitingCommand, Some(FinderCommands::SearchCaseInsensitive)) => {
+                self.search(true);
+                Res::Repeat
+            }
+            _ => {
+                errln!(self.io, "Invalid command");
+                Res::Repeat
+            }
+        }
+    }
+}
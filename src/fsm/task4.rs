@@ -0,0 +1,118 @@
+use crate::common::{errln, outln, Io, LineReader, Res};
+use crate::machine::{commands, Command, GlobalCommands, StateMachine};
+use crate::report::{emit, Format, Record};
+use course_project::task4::parentheses::{parentheses, ParenthesesBalance};
+use std::io::Write;
+
+pub fn run_check(out: &mut impl Write, format: Format, string: &str) {
+    let balance = parentheses(string);
+    let (status, extra_opening) = match balance {
+        ParenthesesBalance::Balanced => ("balanced", 0),
+        ParenthesesBalance::UnmatchedClosing => ("unmatched_closing", 0),
+        ParenthesesBalance::UnmatchedOpening(count) => ("unmatched_opening", count),
+    };
+    let record = Record::new("parens", "check")
+        .input("string", string)
+        .result("status", status)
+        .result("extra_opening", extra_opening);
+    emit(out, format, record, |out| {
+        match balance {
+            ParenthesesBalance::Balanced => outln!(out, "Parentheses are balanced"),
+            ParenthesesBalance::UnmatchedClosing => {
+                outln!(out, "Unmatched ')' parentheses found")
+            }
+            ParenthesesBalance::UnmatchedOpening(count) => outln!(
+                out,
+                "Parentheses are not balanced, extra {} '(' parentheses found",
+                count,
+            ),
+        };
+    });
+}
+
+#[derive(Debug)]
+enum ParenthesesState {
+    Start,
+    AwaitingSting,
+}
+
+commands! {
+    pub enum ParenthesesCommands {
+        EnterString => ("string", "Enter string"),
+    }
+}
+
+pub struct CheckParenthesesStateMachine<'a, R: LineReader, W: Write> {
+    state: ParenthesesState,
+    io: &'a mut Io<R, W>,
+}
+
+impl<'a, R: LineReader, W: Write> CheckParenthesesStateMachine<'a, R, W> {
+    pub fn new(io: &'a mut Io<R, W>) -> Self {
+        CheckParenthesesStateMachine {
+            state: ParenthesesState::Start,
+            io,
+        }
+    }
+
+    fn handle_string(&mut self, input: &str) -> Res<()> {
+        let format = self.io.format();
+        run_check(self.io, format, &self.parse_string(input));
+        self.state = ParenthesesState::Start;
+        Res::Repeat
+    }
+
+    fn parse_string(&self, input: &str) -> String {
+        input.trim().to_string()
+    }
+}
+
+impl<'a, R: LineReader, W: Write> StateMachine<R, W> for CheckParenthesesStateMachine<'a, R, W> {
+    type Command = ParenthesesCommands;
+    type Output = ();
+
+    fn io(&mut self) -> &mut Io<R, W> {
+        self.io
+    }
+
+    fn commands(&self) -> Vec<ParenthesesCommands> {
+        match self.state {
+            ParenthesesState::Start => vec![ParenthesesCommands::EnterString],
+            ParenthesesState::AwaitingSting => vec![],
+        }
+    }
+
+    fn global_commands(&self) -> &'static [GlobalCommands] {
+        match self.state {
+            ParenthesesState::Start => GlobalCommands::ALL,
+            // the string may be any text, even a command
+            ParenthesesState::AwaitingSting => &[],
+        }
+    }
+
+    fn print(&mut self) {
+        let commands = self.commands_text();
+        match self.state {
+            ParenthesesState::Start => {
+                outln!(self.io, "Available commands:\n{}", commands);
+            }
+            ParenthesesState::AwaitingSting => {
+                outln!(self.io, "Enter string for checking parentheses balance");
+            }
+        }
+    }
+
+    fn handle_input(&mut self, input: &str, command: Option<ParenthesesCommands>) -> Res<()> {
+        match (&self.state, command) {
+            (ParenthesesState::Start, Some(ParenthesesCommands::EnterString)) => {
+                self.state = ParenthesesState::AwaitingSting;
+                Res::Repeat
+            }
+            (ParenthesesState::AwaitingSting, _) => self.handle_string(input),
+            _ => {
+                errln!(self.io, "Invalid command");
+                Res::Repeat
+            }
+        }
+    }
+}
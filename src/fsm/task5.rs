@@ -0,0 +1,143 @@
+use crate::common::{errln, outln, Io, LineReader, Res};
+use crate::machine::{commands, StateMachine};
+use crate::report::{emit, Format, Record};
+use course_project::task5::fractal::draw_koch_curve;
+use std::io::{self, Write};
+use std::time::Instant;
+
+pub const DEFAULT_OUTPUT: &str = "koch.svg";
+
+pub fn run_draw(out: &mut impl Write, format: Format, depth: usize, path: &str) -> io::Result<()> {
+    let start = Instant::now();
+    let res = draw_koch_curve(depth, path);
+    let duration = start.elapsed();
+
+    let record = Record::new("koch", "draw")
+        .input("depth", depth)
+        .input("output", path);
+    let record = match &res {
+        Ok(()) => record
+            .result("saved", true)
+            .timing("draw", duration.as_secs_f64()),
+        Err(err) => record
+            .result("saved", false)
+            .result("error", err.to_string()),
+    };
+    emit(out, format, record, |out| match &res {
+        Ok(()) => {
+            outln!(
+                out,
+                "Time elapsed in drawing fractal: {:?}",
+                duration.as_micros()
+            );
+            outln!(out, "You can find the result in {}", path);
+        }
+        Err(err) => outln!(out, "Cannot save {}: {}", path, err),
+    });
+    res
+}
+
+#[derive(Debug)]
+enum FractalDrawerState {
+    Start,
+    AwaitingDepth,
+}
+
+commands! {
+    pub enum DrawerCommands {
+        EnterDepth => ("depth", "Enter fractal depth"),
+    }
+}
+
+pub struct KochCurveDrawerStateMachine<'a, R: LineReader, W: Write> {
+    state: FractalDrawerState,
+    io: &'a mut Io<R, W>,
+}
+
+impl<'a, R: LineReader, W: Write> KochCurveDrawerStateMachine<'a, R, W> {
+    pub fn new(io: &'a mut Io<R, W>) -> Self {
+        KochCurveDrawerStateMachine {
+            state: FractalDrawerState::Start,
+            io,
+        }
+    }
+
+    fn draw(&mut self, input: &str) -> Res<()> {
+        match self.parse_depth(input) {
+            Some(depth) => {
+                let format = self.io.format();
+                // the error was reported, the menu goes on
+                let _ = run_draw(self.io, format, depth, DEFAULT_OUTPUT);
+                Res::Repeat
+            }
+            None => Res::Repeat,
+        }
+    }
+
+    fn parse_depth(&mut self, input: &str) -> Option<usize> {
+        match input.trim().parse::<usize>() {
+            Ok(depth) => Some(depth),
+            Err(_) => {
+                errln!(
+                    self.io,
+                    "Invalid input. Depth must be a number between {} and {}",
+                    usize::MIN,
+                    usize::MAX
+                );
+                None
+            }
+        }
+    }
+}
+
+impl<'a, R: LineReader, W: Write> StateMachine<R, W> for KochCurveDrawerStateMachine<'a, R, W> {
+    type Command = DrawerCommands;
+    type Output = ();
+
+    fn io(&mut self) -> &mut Io<R, W> {
+        self.io
+    }
+
+    fn commands(&self) -> Vec<DrawerCommands> {
+        match self.state {
+            FractalDrawerState::Start => vec![DrawerCommands::EnterDepth],
+            FractalDrawerState::AwaitingDepth => vec![],
+        }
+    }
+
+    fn back(&mut self) -> Res<()> {
+        match self.state {
+            FractalDrawerState::Start => Res::Back,
+            FractalDrawerState::AwaitingDepth => {
+                self.state = FractalDrawerState::Start;
+                Res::Repeat
+            }
+        }
+    }
+
+    fn print(&mut self) {
+        let commands = self.commands_text();
+        match self.state {
+            FractalDrawerState::Start => {
+                outln!(self.io, "Available commands:\n{}", commands);
+            }
+            FractalDrawerState::AwaitingDepth => {
+                outln!(self.io, "Enter fractal depth for drawing or\n{}", commands);
+            }
+        }
+    }
+
+    fn handle_input(&mut self, input: &str, command: Option<DrawerCommands>) -> Res<()> {
+        match (&self.state, command) {
+            (FractalDrawerState::Start, Some(DrawerCommands::EnterDepth)) => {
+                self.state = FractalDrawerState::AwaitingDepth;
+                Res::Repeat
+            }
+            (FractalDrawerState::AwaitingDepth, _) => self.draw(input),
+            _ => {
+                errln!(self.io, "Invalid command");
+                Res::Repeat
+            }
+        }
+    }
+}
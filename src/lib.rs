@@ -0,0 +1,30 @@
+//! Data structures and algorithms from the DSA homework.
+//!
+//! Each task lives in its own module:
+//!
+//! * [`task1`] - comparison sorts ([`quicksort()`], [`introsort()`], [`selection_sort()`],
+//!   [`merge_sort()`], [`heap_sort()`], [`shell_sort()`], [`insertion_sort()`] and their variants);
+//! * [`task2`] - Fibonacci search over sorted vectors and a fixed-size hash set with linear rehashing;
+//! * [`task3`] - simplified Boyer-Moore substring search;
+//! * [`task4`] - a bounded linked-list stack and a parentheses balance checker built on it;
+//! * [`task5`] - Koch curve rendering to SVG.
+//!
+//! The most used items are re-exported from the crate root.
+#![warn(missing_docs)]
+
+pub mod task1;
+pub mod task2;
+pub mod task3;
+pub mod task4;
+pub mod task5;
+
+pub use task1::{
+    heap_sort, heap_sort_by, heap_sort_by_key, insertion_sort, insertion_sort_by,
+    insertion_sort_by_key, introsort, introsort_by, introsort_by_key, merge_sort, merge_sort_by,
+    merge_sort_by_key, quicksort, quicksort_by, quicksort_by_key, selection_sort,
+    selection_sort_by, selection_sort_by_key, shell_sort, shell_sort_by, shell_sort_by_key,
+};
+pub use task2::{fib_search, CustomHasSet};
+pub use task3::boyer_moor_search;
+pub use task4::{parentheses, ParenthesesBalance, Stack};
+pub use task5::draw_koch_curve;
@@ -0,0 +1,310 @@
+use crate::common::{outln, InvalidEnumValueError, Io, LineReader, Res};
+use crate::report::{emit, Format, Record};
+use std::io::Write;
+
+/// A command understood by a state machine.
+///
+/// Implemented by the enums declared with [`commands!`].
+pub trait Command: Copy + 'static {
+    /// Every command of the enum, in the order they are listed to the user.
+    const ALL: &'static [Self];
+
+    /// The text the user types to run the command.
+    fn name(&self) -> &'static str;
+
+    /// One-line help text.
+    fn description(&self) -> &'static str;
+
+    fn from_text(value: &str) -> Result<Self, InvalidEnumValueError> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|command| command.name() == value)
+            .ok_or(InvalidEnumValueError)
+    }
+
+    fn to_text(&self) -> String {
+        format!("'{}' - {}", self.name(), self.description())
+    }
+}
+
+/// Declares a command enum with the text and help of every variant and implements [`Command`] for it.
+macro_rules! commands {
+    ($vis:vis enum $name:ident { $($variant:ident => ($text:literal, $description:literal)),* $(,)? }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis enum $name {
+            $($variant),*
+        }
+
+        impl $crate::machine::Command for $name {
+            const ALL: &'static [Self] = &[$($name::$variant),*];
+
+            fn name(&self) -> &'static str {
+                match *self {
+                    $($name::$variant => $text),*
+                }
+            }
+
+            fn description(&self) -> &'static str {
+                match *self {
+                    $($name::$variant => $description),*
+                }
+            }
+        }
+    };
+}
+pub(crate) use commands;
+
+commands! {
+    pub enum GlobalCommands {
+        Help => ("help", "Print commands available now"),
+        Back => ("back", "Return to the previous step or menu"),
+        MainMenu => ("main", "Return to main menu"),
+        Exit => ("exit", "Exit program"),
+    }
+}
+
+/// A menu driven by lines of user input.
+///
+/// Implementors describe their commands and how to react to a line of input, [`StateMachine::start`]
+/// runs the read-handle loop.
+pub trait StateMachine<R: LineReader, W: Write> {
+    type Command: Command;
+    type Output;
+
+    fn io(&mut self) -> &mut Io<R, W>;
+
+    /// Commands of the machine accepted in the current state.
+    fn commands(&self) -> Vec<Self::Command>;
+
+    /// Commands handled by the driver for this machine in the current state.
+    ///
+    /// States reading free text return none, so that every line is passed to
+    /// [`StateMachine::handle_input`] as a value, e.g. a string named 'back'.
+    fn global_commands(&self) -> &'static [GlobalCommands] {
+        &[
+            GlobalCommands::Help,
+            GlobalCommands::Back,
+            GlobalCommands::MainMenu,
+            GlobalCommands::Exit,
+        ]
+    }
+
+    /// Handles the global `back` command: steps back to the previous state of the machine and
+    /// returns [`Res::Repeat`], or returns [`Res::Back`] to leave the machine.
+    fn back(&mut self) -> Res<Self::Output> {
+        Res::Back
+    }
+
+    /// Prints the prompt for the current state. Not called in JSON format.
+    fn print(&mut self);
+
+    /// Handles a line of input. `command` is the parsed command if the line is one.
+    fn handle_input(&mut self, input: &str, command: Option<Self::Command>) -> Res<Self::Output>;
+
+    /// Help text of all commands available in the current state, one per line.
+    fn commands_text(&self) -> String {
+        self.commands()
+            .iter()
+            .map(Command::to_text)
+            .chain(self.global_commands().iter().map(Command::to_text))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Prints the commands available in the current state with their descriptions.
+    fn print_help(&mut self) {
+        let names = self
+            .commands()
+            .iter()
+            .map(Command::name)
+            .chain(self.global_commands().iter().map(Command::name))
+            .collect::<Vec<&str>>();
+        let commands = self.commands_text();
+        let format = self.io().format();
+        let record = Record::new("session", "help").result("commands", names);
+        emit(self.io(), format, record, |out| {
+            outln!(out, "Available commands:\n{}", commands)
+        });
+    }
+
+    /// Runs the machine until it produces anything but [`Res::Repeat`].
+    ///
+    /// The end of input and an input error in strict mode are treated as [`Res::Exit`].
+    fn start(&mut self) -> Res<Self::Output> {
+        let mut buffer = String::new();
+
+        loop {
+            if self.io().format() == Format::Text {
+                self.print();
+            }
+            let completions = self
+                .commands()
+                .iter()
+                .map(Command::name)
+                .chain(self.global_commands().iter().map(Command::name))
+                .collect();
+            self.io().set_completions(completions);
+            buffer.clear();
+            match self.io().read_line(&mut buffer) {
+                Ok(0) => return Res::Exit,
+                Ok(_) => (),
+                Err(_) => {
+                    outln!(self.io(), "Error reading input");
+                    continue;
+                }
+            }
+
+            let res = match GlobalCommands::from_text(buffer.trim()) {
+                Ok(global) if self.global_commands().contains(&global) => match global {
+                    GlobalCommands::Help => {
+                        self.print_help();
+                        Res::Repeat
+                    }
+                    GlobalCommands::Back => self.back(),
+                    GlobalCommands::MainMenu => Res::MainMenu,
+                    GlobalCommands::Exit => Res::Exit,
+                },
+                _ => {
+                    let command = Self::Command::from_text(buffer.trim()).ok();
+                    self.handle_input(&buffer, command)
+                }
+            };
+            if self.io().is_failed() {
+                return Res::Exit;
+            }
+            match res {
+                Res::Repeat => (),
+                res => return res,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::errln;
+    use std::io::Cursor;
+
+    type TestIo<'a> = Io<Cursor<&'static str>, &'a mut Vec<u8>>;
+
+    commands! {
+        enum CounterCommands {
+            Add => ("add", "Add one"),
+            Done => ("done", "Return the count"),
+        }
+    }
+
+    // counts the 'add' commands until 'done'
+    struct Counter<'a, 'b> {
+        io: &'a mut TestIo<'b>,
+        count: usize,
+    }
+
+    impl<'a, 'b> StateMachine<Cursor<&'static str>, &'b mut Vec<u8>> for Counter<'a, 'b> {
+        type Command = CounterCommands;
+        type Output = usize;
+
+        fn io(&mut self) -> &mut TestIo<'b> {
+            self.io
+        }
+
+        fn commands(&self) -> Vec<CounterCommands> {
+            CounterCommands::ALL.to_vec()
+        }
+
+        fn print(&mut self) {
+            outln!(self.io, "Count: {}", self.count);
+        }
+
+        fn handle_input(&mut self, _input: &str, command: Option<CounterCommands>) -> Res<usize> {
+            match command {
+                Some(CounterCommands::Add) => {
+                    self.count += 1;
+                    Res::Repeat
+                }
+                Some(CounterCommands::Done) => Res::Result(self.count),
+                None => {
+                    errln!(self.io, "Invalid command");
+                    Res::Repeat
+                }
+            }
+        }
+    }
+
+    // runs a counter on the script, returns its result, the transcript and the input errors
+    fn run(
+        script: &'static str,
+        configure: impl FnOnce(TestIo) -> TestIo,
+    ) -> (Res<usize>, String, Vec<String>) {
+        let mut output = vec![];
+        let mut io = configure(Io::new(Cursor::new(script), &mut output));
+        let res = Counter {
+            io: &mut io,
+            count: 0,
+        }
+        .start();
+        let errors = io.errors().to_vec();
+        drop(io);
+        (res, String::from_utf8(output).unwrap(), errors)
+    }
+
+    #[test]
+    fn end_of_input_exits() {
+        let (res, transcript, _) = run("", |io| io);
+        assert!(matches!(res, Res::Exit));
+        assert_eq!(transcript, "Count: 0\n");
+        let (res, _, _) = run("add\nadd", |io| io);
+        assert!(matches!(res, Res::Exit));
+    }
+
+    #[test]
+    fn runs_commands_until_a_result() {
+        let (res, transcript, errors) = run("add\n add \nadd\ndone\nadd\n", |io| io);
+        assert!(matches!(res, Res::Result(3)));
+        assert_eq!(transcript, "Count: 0\nCount: 1\nCount: 2\nCount: 3\n");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn handles_global_commands() {
+        let (res, transcript, _) = run("help\nback\n", |io| io);
+        assert!(matches!(res, Res::Back));
+        assert!(transcript
+            .contains("Available commands:\n'add' - Add one\n'done' - Return the count\n'help'"));
+        assert!(transcript.contains("'exit' - Exit program\n"));
+        assert!(matches!(run("main\n", |io| io).0, Res::MainMenu));
+        assert!(matches!(run("exit\ndone\n", |io| io).0, Res::Exit));
+    }
+
+    #[test]
+    fn echoes_the_script() {
+        let (_, transcript, _) = run("add\ndone\n", |io| io.with_echo(true));
+        assert_eq!(transcript, "Count: 0\n> add\nCount: 1\n> done\n");
+    }
+
+    #[test]
+    fn reports_input_errors() {
+        let (res, transcript, errors) = run("add\nsub\ndone\n", |io| io);
+        assert!(matches!(res, Res::Result(1)));
+        assert!(transcript.contains("Invalid command\n"));
+        assert_eq!(errors, ["line 2: Invalid command"]);
+
+        let (res, _, errors) = run("add\nsub\ndone\n", |io| io.with_strict(true));
+        assert!(matches!(res, Res::Exit));
+        assert_eq!(errors, ["line 2: Invalid command"]);
+    }
+
+    #[test]
+    fn prints_no_prompts_in_json() {
+        let (res, transcript, _) = run("add\nhelp\ndone\n", |io| {
+            io.with_format(Format::Json).with_echo(true)
+        });
+        assert!(matches!(res, Res::Result(1)));
+        assert_eq!(
+            transcript,
+            "{\"task\":\"session\",\"operation\":\"help\",\"inputs\":{},\"result\":{\"commands\":[\"add\",\"done\",\"help\",\"back\",\"main\",\"exit\"]},\"timings\":{}}\n"
+        );
+    }
+}
@@ -1,15 +1,30 @@
-use crate::fsm::MainStateMachine;
+use crate::cli::CliError;
+use std::io;
 
+mod chart;
+mod cli;
 mod common;
+mod dataset;
+mod distribution;
+mod editor;
 mod fsm;
-mod task1;
-mod task2;
-mod task3;
-mod task4;
-mod task5;
+mod machine;
+mod report;
+mod statistics;
+mod tasks;
 
 fn main() {
-    // task3::main();
-    let stdin = std::io::stdin();
-    MainStateMachine::new(&stdin).start();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::run(&mut io::stdout(), &args) {
+        Ok(()) => (),
+        Err(CliError::Usage(message)) => {
+            eprintln!("{}\n\n{}", message, cli::usage());
+            std::process::exit(2);
+        }
+        Err(CliError::Failed(message)) => {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+        Err(CliError::Reported) => std::process::exit(1),
+    }
 }
@@ -0,0 +1,309 @@
+use crate::common::outln;
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+/// How task results are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    // human readable text
+    Text,
+    // one JSON record per operation, see `Record`
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format '{}'", value)),
+        }
+    }
+}
+
+/// Minimal JSON value, enough to serialize task results.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Int(value) => write!(f, "{}", value),
+            Json::Float(value) if value.is_finite() => write!(f, "{}", value),
+            Json::Float(_) => write!(f, "null"),
+            Json::String(value) => write_string(f, value),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for char in value.chars() {
+        match char {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<i32> for Json {
+    fn from(value: i32) -> Self {
+        Json::Int(value as i64)
+    }
+}
+
+impl From<u64> for Json {
+    fn from(value: u64) -> Self {
+        Json::Int(value as i64)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Json::Int(value as i64)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Json::Float(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(value: Vec<T>) -> Self {
+        Json::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Json> + Clone> From<&[T]> for Json {
+    fn from(value: &[T]) -> Self {
+        Json::Array(value.iter().cloned().map(Into::into).collect())
+    }
+}
+
+/// Structured result of a single operation, written as one line of JSON.
+pub struct Record {
+    task: &'static str,
+    operation: &'static str,
+    inputs: Vec<(String, Json)>,
+    result: Vec<(String, Json)>,
+    // seconds
+    timings: Vec<(String, Json)>,
+}
+
+impl Record {
+    pub fn new(task: &'static str, operation: &'static str) -> Self {
+        Record {
+            task,
+            operation,
+            inputs: vec![],
+            result: vec![],
+            timings: vec![],
+        }
+    }
+
+    pub fn input(mut self, name: &str, value: impl Into<Json>) -> Self {
+        self.inputs.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn result(mut self, name: &str, value: impl Into<Json>) -> Self {
+        self.result.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn timing(mut self, name: &str, seconds: f64) -> Self {
+        self.timings.push((name.to_string(), seconds.into()));
+        self
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = |items: &[(String, Json)]| Json::Object(items.to_vec());
+        write!(
+            f,
+            "{}",
+            Json::Object(vec![
+                ("task".to_string(), self.task.into()),
+                ("operation".to_string(), self.operation.into()),
+                ("inputs".to_string(), fields(&self.inputs)),
+                ("result".to_string(), fields(&self.result)),
+                ("timings".to_string(), fields(&self.timings)),
+            ])
+        )
+    }
+}
+
+/// Writes `record` as a line of JSON, or calls `text` to print the result as text.
+pub fn emit<W: Write>(out: &mut W, format: Format, record: Record, text: impl FnOnce(&mut W)) {
+    match format {
+        Format::Text => text(out),
+        Format::Json => outln!(out, "{}", record),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fsm::task3::run_search;
+    use crate::fsm::task4::run_check;
+
+    #[test]
+    fn escapes_strings() {
+        let value = Json::from("say \"hi\"\\ \n\r\t\u{0}\u{1f} é ✓");
+        assert_eq!(
+            value.to_string(),
+            r#""say \"hi\"\\ \n\r\t\u0000\u001f é ✓""#
+        );
+        let object = Json::Object(vec![("a\"b".to_string(), "\\".into())]);
+        assert_eq!(object.to_string(), r#"{"a\"b":"\\"}"#);
+    }
+
+    #[test]
+    fn writes_values() {
+        let array = Json::from(vec![
+            Json::Null,
+            true.into(),
+            (-3).into(),
+            0.5.into(),
+            f64::NAN.into(),
+            f64::INFINITY.into(),
+            Json::from(None::<i32>),
+            Json::from(vec![1, 2]),
+            Json::Object(vec![]),
+        ]);
+        assert_eq!(
+            array.to_string(),
+            "[null,true,-3,0.5,null,null,null,[1,2],{}]"
+        );
+    }
+
+    #[test]
+    fn writes_records() {
+        let record = Record::new("sort", "sort")
+            .input("name", "quick \"first\"")
+            .input("size", 3usize)
+            .result("sorted", vec![1, 2, 3])
+            .timing("quick", 0.25);
+        assert_eq!(
+            record.to_string(),
+            concat!(
+                r#"{"task":"sort","operation":"sort","#,
+                r#""inputs":{"name":"quick \"first\"","size":3},"#,
+                r#""result":{"sorted":[1,2,3]},"timings":{"quick":0.25}}"#
+            )
+        );
+        let empty = Record::new("session", "help");
+        assert_eq!(
+            empty.to_string(),
+            r#"{"task":"session","operation":"help","inputs":{},"result":{},"timings":{}}"#
+        );
+    }
+
+    #[test]
+    fn emits_text_or_one_record() {
+        let mut out = vec![];
+        emit(&mut out, Format::Text, Record::new("a", "b"), |out| {
+            outln!(out, "text")
+        });
+        assert_eq!(String::from_utf8(out).unwrap(), "text\n");
+
+        let mut out = vec![];
+        emit(&mut out, Format::Json, Record::new("a", "b"), |_| {
+            panic!("text is not written in JSON")
+        });
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"task\":\"a\",\"operation\":\"b\",\"inputs\":{},\"result\":{},\"timings\":{}}\n"
+        );
+    }
+
+    #[test]
+    fn writes_one_record_per_operation() {
+        let mut out = vec![];
+        run_check(&mut out, Format::Json, "(\"()");
+        run_search(&mut out, Format::Json, "a\tbab", "b", false);
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            concat!(
+                r#"{"task":"parens","operation":"check","inputs":{"string":"(\"()"},"#,
+                r#""result":{"status":"unmatched_opening","extra_opening":1},"timings":{}}"#
+            )
+        );
+        assert!(lines[1].starts_with(concat!(
+            r#"{"task":"search","operation":"search","#,
+            r#""inputs":{"string":"a\tbab","pattern":"b","case_insensitive":false},"#,
+            r#""result":{"sbm":[2,4],"std":[2,4]},"timings":{"sbm":"#
+        )));
+        assert!(lines[1].ends_with("}}"));
+    }
+}
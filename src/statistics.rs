@@ -0,0 +1,239 @@
+use crate::common::find_average;
+
+// two-sided 95% critical values of Student's t distribution by degrees of freedom,
+// a larger number of degrees uses the closest smaller entry
+const T_95: [(usize, f64); 33] = [
+    (1, 12.706),
+    (2, 4.303),
+    (3, 3.182),
+    (4, 2.776),
+    (5, 2.571),
+    (6, 2.447),
+    (7, 2.365),
+    (8, 2.306),
+    (9, 2.262),
+    (10, 2.228),
+    (11, 2.201),
+    (12, 2.179),
+    (13, 2.160),
+    (14, 2.145),
+    (15, 2.131),
+    (16, 2.120),
+    (17, 2.110),
+    (18, 2.101),
+    (19, 2.093),
+    (20, 2.086),
+    (21, 2.080),
+    (22, 2.074),
+    (23, 2.069),
+    (24, 2.064),
+    (25, 2.060),
+    (26, 2.056),
+    (27, 2.052),
+    (28, 2.048),
+    (29, 2.045),
+    (30, 2.042),
+    (40, 2.021),
+    (60, 2.000),
+    (120, 1.980),
+];
+
+/// Descriptive statistics of a series of measurements.
+#[derive(Debug, Clone, Copy)]
+pub struct Summary {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+    /// Sample standard deviation, NaN for a single measurement.
+    pub std_dev: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p95: f64,
+    /// 95% confidence interval of the mean, NaN for a single measurement.
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl Summary {
+    /// Summarizes the measurements, `None` if there are none.
+    pub fn of(values: &[f64]) -> Option<Summary> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable_by(f64::total_cmp);
+        let count = sorted.len();
+        let mean = find_average(&sorted);
+        let std_dev = match count {
+            1 => f64::NAN,
+            _ => {
+                let squares = sorted
+                    .iter()
+                    .map(|value| (value - mean).powi(2))
+                    .sum::<f64>();
+                (squares / (count - 1) as f64).sqrt()
+            }
+        };
+        let margin = t_95(count.saturating_sub(1)) * std_dev / (count as f64).sqrt();
+        Some(Summary {
+            count,
+            min: sorted[0],
+            max: sorted[count - 1],
+            mean,
+            median: percentile(&sorted, 50.0),
+            std_dev,
+            p5: percentile(&sorted, 5.0),
+            p25: percentile(&sorted, 25.0),
+            p75: percentile(&sorted, 75.0),
+            p95: percentile(&sorted, 95.0),
+            ci_low: mean - margin,
+            ci_high: mean + margin,
+        })
+    }
+
+    /// Named values in the order of [`Summary::COLUMNS`].
+    pub fn values(&self) -> [f64; 11] {
+        [
+            self.min,
+            self.p5,
+            self.p25,
+            self.median,
+            self.p75,
+            self.p95,
+            self.max,
+            self.mean,
+            self.std_dev,
+            self.ci_low,
+            self.ci_high,
+        ]
+    }
+
+    /// Names of [`Summary::values`].
+    pub const COLUMNS: [&'static str; 11] = [
+        "min", "p5", "p25", "median", "p75", "p95", "max", "mean", "std_dev", "ci_low", "ci_high",
+    ];
+}
+
+fn t_95(degrees: usize) -> f64 {
+    match degrees {
+        0 => f64::NAN,
+        _ => T_95
+            .iter()
+            .rev()
+            .find(|(entry, _)| *entry <= degrees)
+            .map_or(f64::NAN, |(_, t)| *t),
+    }
+}
+
+// percentile of sorted values with linear interpolation between the closest ranks
+fn percentile(sorted: &[f64], percent: f64) -> f64 {
+    let rank = percent / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn summarizes_nothing_as_none() {
+        assert!(Summary::of(&[]).is_none());
+    }
+
+    #[test]
+    fn summarizes_one_sample() {
+        let summary = Summary::of(&[3.0]).unwrap();
+        assert_eq!(summary.count, 1);
+        for value in [
+            summary.min,
+            summary.p5,
+            summary.median,
+            summary.p95,
+            summary.max,
+            summary.mean,
+        ] {
+            assert_eq!(value, 3.0);
+        }
+        assert!(summary.std_dev.is_nan());
+        assert!(summary.ci_low.is_nan() && summary.ci_high.is_nan());
+    }
+
+    #[test]
+    fn summarizes_two_samples() {
+        let summary = Summary::of(&[3.0, 1.0]).unwrap();
+        assert_eq!((summary.count, summary.min, summary.max), (2, 1.0, 3.0));
+        assert_close(summary.mean, 2.0);
+        assert_close(summary.median, 2.0);
+        assert_close(summary.std_dev, 2f64.sqrt());
+        assert_close(summary.p5, 1.1);
+        assert_close(summary.p95, 2.9);
+        // one degree of freedom, the margin is t * s / sqrt(2) = t
+        assert_close(summary.ci_low, 2.0 - 12.706);
+        assert_close(summary.ci_high, 2.0 + 12.706);
+    }
+
+    #[test]
+    fn interpolates_percentiles() {
+        let summary = Summary::of(&[4.0, 1.0, 3.0, 2.0]).unwrap();
+        assert_close(summary.median, 2.5);
+        assert_close(summary.p25, 1.75);
+        assert_close(summary.p75, 3.25);
+
+        let values = (0..=10).rev().map(f64::from).collect::<Vec<f64>>();
+        let summary = Summary::of(&values).unwrap();
+        assert_close(summary.median, 5.0);
+        assert_close(summary.p5, 0.5);
+        assert_close(summary.p95, 9.5);
+        assert_eq!(
+            summary.values(),
+            [
+                summary.min,
+                summary.p5,
+                summary.p25,
+                summary.median,
+                summary.p75,
+                summary.p95,
+                summary.max,
+                summary.mean,
+                summary.std_dev,
+                summary.ci_low,
+                summary.ci_high
+            ]
+        );
+    }
+
+    #[test]
+    fn confidence_interval_uses_the_t_table() {
+        let values = (1..=10).map(f64::from).collect::<Vec<f64>>();
+        let summary = Summary::of(&values).unwrap();
+        let std_dev = (82.5f64 / 9.0).sqrt();
+        assert_close(summary.std_dev, std_dev);
+        let margin = 2.262 * std_dev / 10f64.sqrt();
+        assert_close(summary.ci_low, 5.5 - margin);
+        assert_close(summary.ci_high, 5.5 + margin);
+    }
+
+    #[test]
+    fn t_95_takes_the_closest_smaller_entry() {
+        assert!(t_95(0).is_nan());
+        assert_eq!(t_95(1), 12.706);
+        assert_eq!(t_95(9), 2.262);
+        assert_eq!(t_95(30), 2.042);
+        assert_eq!(t_95(39), 2.042);
+        assert_eq!(t_95(40), 2.021);
+        assert_eq!(t_95(100_000), 1.980);
+    }
+}
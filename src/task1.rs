@@ -1,3 +1,79 @@
-pub mod fsm;
-mod quicksort;
-mod selection_sort;
+//! In-place sorting algorithms: comparison sorts for slices of [`Ord`] elements and
+//! non-comparison sorts for `i32` slices ([`counting_sort()`], [`lsd_radix_sort`], [`msd_radix_sort`],
+//! [`bucket_sort()`]). [`is_stable`] checks whether a sort keeps the order of equal elements.
+//!
+//! Like [`slice::sort_by`] and [`slice::sort_by_key`], every comparison sort has a `_by` variant
+//! taking a comparator and a `_by_key` variant taking a key function, e.g. [`quicksort_by`] and
+//! [`quicksort_by_key`]. The integer sorts have a `_by_key` variant sorting any elements by an `i32`
+//! key.
+//!
+//! [`parallel_merge_sort`] and [`parallel_quicksort`] split the work over scoped threads.
+//! [`ExternalSort`] sorts files of integers larger than the memory.
+//!
+//! The `_probed` variants report the moves of a sort to a [`Probe`]. [`trace`](trace()) records the
+//! comparisons of a sort on [`Traced`] elements and its moves reported to the [`Tracing`] probe,
+//! [`sort_animation`] renders them as an animated SVG.
+
+pub mod animation;
+pub mod bucket_sort;
+#[cfg(test)]
+mod check;
+pub mod counters;
+pub mod counting_sort;
+pub mod external;
+pub mod heap_sort;
+pub mod insertion_sort;
+pub mod introsort;
+pub mod merge_sort;
+pub mod parallel;
+pub mod probe;
+pub mod quicksort;
+pub mod radix_sort;
+pub mod selection_sort;
+pub mod shell_sort;
+pub mod stability;
+pub mod trace;
+
+pub use animation::{draw_sort_animation, sort_animation};
+pub use bucket_sort::{bucket_sort, bucket_sort_by_key, bucket_sort_probed};
+pub use counters::{count_operations, Counted, Counting, Operations};
+pub use counting_sort::{counting_sort, counting_sort_by_key, counting_sort_probed};
+pub use external::{read_values, write_values, ExternalSort, ExternalStats, FileFormat};
+pub use heap_sort::{heap_sort, heap_sort_by, heap_sort_by_key, heap_sort_probed};
+pub use insertion_sort::{
+    binary_insertion_sort, binary_insertion_sort_by, binary_insertion_sort_by_key,
+    binary_insertion_sort_probed, insertion_sort, insertion_sort_by, insertion_sort_by_key,
+    insertion_sort_probed,
+};
+pub use introsort::{
+    introsort, introsort_by, introsort_by_key, introsort_with, introsort_with_by,
+    introsort_with_by_key, introsort_with_probed,
+};
+pub use merge_sort::{
+    bottom_up_merge_sort, bottom_up_merge_sort_by, bottom_up_merge_sort_by_key,
+    bottom_up_merge_sort_probed, merge_sort, merge_sort_by, merge_sort_by_key, merge_sort_probed,
+};
+pub use parallel::{
+    default_threads, parallel_merge_sort, parallel_merge_sort_by, parallel_merge_sort_by_key,
+    parallel_merge_sort_with, parallel_merge_sort_with_by, parallel_merge_sort_with_by_key,
+    parallel_quicksort, parallel_quicksort_by, parallel_quicksort_by_key, parallel_quicksort_with,
+    parallel_quicksort_with_by, parallel_quicksort_with_by_key,
+};
+pub use probe::Probe;
+pub use quicksort::{
+    quicksort, quicksort_by, quicksort_by_key, quicksort_with, quicksort_with_by,
+    quicksort_with_by_key, quicksort_with_probed, stable_quicksort, stable_quicksort_by,
+    stable_quicksort_by_key, stable_quicksort_probed, Partition, Pivot,
+};
+pub use radix_sort::{
+    lsd_radix_sort, lsd_radix_sort_by_key, lsd_radix_sort_probed, msd_radix_sort,
+    msd_radix_sort_by_key, msd_radix_sort_probed,
+};
+pub use selection_sort::{
+    selection_sort, selection_sort_by, selection_sort_by_key, selection_sort_probed,
+    stable_selection_sort, stable_selection_sort_by, stable_selection_sort_by_key,
+    stable_selection_sort_probed,
+};
+pub use shell_sort::{shell_sort, shell_sort_by, shell_sort_by_key, shell_sort_probed, Gaps};
+pub use stability::{is_stable, Tagged};
+pub use trace::{trace, Step, Traced, Tracing};
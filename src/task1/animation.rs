@@ -0,0 +1,297 @@
+//! Animated SVG of a sort traced with [`trace`](crate::task1::trace()).
+
+use crate::task1::trace::Step;
+use std::io;
+use std::path::Path;
+use svg::node::element::{Animate, AnimateTransform, Group, Rectangle, Text};
+use svg::Document;
+
+const MARGIN: f64 = 30.;
+const SLOT_WIDTH: f64 = 24.;
+const BAR_WIDTH: f64 = 20.;
+const CHART_HEIGHT: f64 = 240.;
+const MIN_BAR_HEIGHT: f64 = 6.;
+const HEADER_HEIGHT: f64 = 50.;
+const FONT_SIZE: f64 = 12.;
+const MIN_WIDTH: f64 = 420.;
+
+// part of a step a move takes, the rest the bars stand still
+const MOVE_SHARE: f64 = 0.8;
+
+// steps the animation stays on the initial and on the sorted array
+const PAUSE_BEFORE: usize = 1;
+const PAUSE_AFTER: usize = 4;
+
+const IDLE: &str = "#1f77b4";
+const COMPARED: &str = "#ff7f0e";
+const MOVED: &str = "#d62728";
+const PIVOT: &str = "#2ca02c";
+
+fn text(x: f64, y: f64, anchor: &str, content: impl Into<String>) -> Text {
+    Text::new()
+        .add(svg::node::Text::new(content))
+        .set("x", x)
+        .set("y", y)
+        .set("font-family", "sans-serif")
+        .set("font-size", FONT_SIZE)
+        .set("text-anchor", anchor)
+}
+
+// `values` and `keyTimes` of an animation from `(time, value)` pairs
+fn keyframes<V: ToString>(frames: &[(f64, V)], duration: f64) -> (String, String) {
+    let values = frames
+        .iter()
+        .map(|(_, value)| value.to_string())
+        .collect::<Vec<String>>();
+    let times = frames
+        .iter()
+        .map(|(time, _)| format!("{:.5}", time / duration))
+        .collect::<Vec<String>>();
+    (values.join(";"), times.join(";"))
+}
+
+// position and colour of every element over time, elements are named by their initial position
+struct Timeline {
+    // element at every position
+    order: Vec<usize>,
+    pivot: Option<usize>,
+    colors: Vec<&'static str>,
+    x_frames: Vec<Vec<(f64, f64)>>,
+    color_frames: Vec<Vec<(f64, &'static str)>>,
+}
+
+impl Timeline {
+    fn new(len: usize) -> Self {
+        Timeline {
+            order: (0..len).collect(),
+            pivot: None,
+            colors: vec![IDLE; len],
+            x_frames: (0..len).map(|i| vec![(0., slot_x(i))]).collect(),
+            color_frames: vec![vec![(0., IDLE)]; len],
+        }
+    }
+
+    // applies the step starting at `time`, returns the elements it highlights
+    fn apply(&mut self, step: Step, time: f64, seconds: f64) -> Vec<(usize, &'static str)> {
+        let before = self.order.clone();
+        let highlighted = match step {
+            Step::Compare(a, b) => vec![(before[a], COMPARED), (before[b], COMPARED)],
+            Step::Swap(a, b) => {
+                self.order.swap(a, b);
+                vec![(before[a], MOVED), (before[b], MOVED)]
+            }
+            Step::Rotate(first, last) => {
+                self.order[first..=last].rotate_right(1);
+                (first..=last).map(|i| (before[i], MOVED)).collect()
+            }
+            Step::Pivot(position) => {
+                self.pivot = Some(before[position]);
+                vec![]
+            }
+        };
+        for (position, &element) in self.order.iter().enumerate() {
+            if before[position] != element {
+                let frames = &mut self.x_frames[element];
+                let from = frames.last().map_or(0., |&(_, x)| x);
+                frames.push((time, from));
+                frames.push((time + seconds * MOVE_SHARE, slot_x(position)));
+            }
+        }
+        highlighted
+    }
+
+    // colours every element from `time` on, the highlighted ones and the pivot differ
+    fn paint(&mut self, time: f64, highlighted: &[(usize, &'static str)]) {
+        for element in 0..self.colors.len() {
+            let color = highlighted
+                .iter()
+                .find(|(highlighted, _)| *highlighted == element)
+                .map(|&(_, color)| color)
+                .unwrap_or(match self.pivot == Some(element) {
+                    true => PIVOT,
+                    false => IDLE,
+                });
+            if self.colors[element] != color {
+                self.colors[element] = color;
+                self.color_frames[element].push((time, color));
+            }
+        }
+    }
+}
+
+fn slot_x(position: usize) -> f64 {
+    MARGIN + position as f64 * SLOT_WIDTH + (SLOT_WIDTH - BAR_WIDTH) / 2.
+}
+
+/// Renders the sort of `values` recorded in `steps` as bars changing places over time.
+///
+/// Every step takes `step_seconds`: compared elements are highlighted in orange and moved ones in
+/// red, the current pivot stays green until the next one is chosen. The animation repeats after
+/// pausing on the sorted array.
+pub fn sort_animation(title: &str, values: &[i32], steps: &[Step], step_seconds: f64) -> Document {
+    let len = values.len();
+    let width = (2. * MARGIN + len as f64 * SLOT_WIDTH).max(MIN_WIDTH);
+    let baseline = MARGIN + HEADER_HEIGHT + CHART_HEIGHT;
+    let height = baseline + 3. * FONT_SIZE + MARGIN;
+    let end = (PAUSE_BEFORE + steps.len()) as f64 * step_seconds;
+    let duration = end + PAUSE_AFTER as f64 * step_seconds;
+
+    let mut timeline = Timeline::new(len);
+    for (i, &step) in steps.iter().enumerate() {
+        let time = (PAUSE_BEFORE + i) as f64 * step_seconds;
+        let highlighted = timeline.apply(step, time, step_seconds);
+        timeline.paint(time, &highlighted);
+    }
+    timeline.pivot = None;
+    timeline.paint(end, &[]);
+
+    let count = |matches: fn(&Step) -> bool| steps.iter().filter(|step| matches(step)).count();
+    let caption = format!(
+        "{} elements, {} comparisons, {} swaps, {} rotations",
+        len,
+        count(|step| matches!(step, Step::Compare(..))),
+        count(|step| matches!(step, Step::Swap(..))),
+        count(|step| matches!(step, Step::Rotate(..)))
+    );
+    let legend = [(COMPARED, "compared"), (MOVED, "moved"), (PIVOT, "pivot")]
+        .iter()
+        .enumerate()
+        .fold(Group::new(), |group, (i, &(color, label))| {
+            let x = MARGIN + i as f64 * 100.;
+            let y = MARGIN + 2.5 * FONT_SIZE;
+            group
+                .add(
+                    Rectangle::new()
+                        .set("x", x)
+                        .set("y", y)
+                        .set("width", FONT_SIZE)
+                        .set("height", FONT_SIZE)
+                        .set("fill", color),
+                )
+                .add(text(
+                    x + FONT_SIZE * 1.5,
+                    y + FONT_SIZE - 2.,
+                    "start",
+                    label,
+                ))
+        });
+
+    let (min, max) = values
+        .iter()
+        .fold((i32::MAX, i32::MIN), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        });
+    let bar_height = |value: i32| match max > min {
+        true => {
+            MIN_BAR_HEIGHT
+                + (value as f64 - min as f64) / (max as f64 - min as f64)
+                    * (CHART_HEIGHT - MIN_BAR_HEIGHT)
+        }
+        false => CHART_HEIGHT / 2.,
+    };
+    let dur = format!("{}s", duration);
+    let bars = values
+        .iter()
+        .enumerate()
+        .fold(Group::new(), |group, (element, &value)| {
+            let mut x_frames = timeline.x_frames[element].clone();
+            let last = x_frames.last().map_or(0., |&(_, x)| x);
+            x_frames.push((duration, last));
+            let translations = x_frames
+                .iter()
+                .map(|&(time, x)| (time, format!("{},0", x)))
+                .collect::<Vec<(f64, String)>>();
+            let (positions, position_times) = keyframes(&translations, duration);
+            let (colors, color_times) = keyframes(&timeline.color_frames[element], duration);
+            let bar_height = bar_height(value);
+            let bar = Rectangle::new()
+                .set("x", 0)
+                .set("y", baseline - bar_height)
+                .set("width", BAR_WIDTH)
+                .set("height", bar_height)
+                .set("fill", IDLE)
+                .add(
+                    Animate::new()
+                        .set("attributeName", "fill")
+                        .set("values", colors)
+                        .set("keyTimes", color_times)
+                        .set("calcMode", "discrete")
+                        .set("dur", dur.as_str())
+                        .set("repeatCount", "indefinite"),
+                );
+            let label = text(
+                BAR_WIDTH / 2.,
+                baseline + 1.5 * FONT_SIZE,
+                "middle",
+                value.to_string(),
+            )
+            .set("font-size", FONT_SIZE * 0.8);
+            group.add(
+                Group::new()
+                    .set("transform", format!("translate({},0)", slot_x(element)))
+                    .add(bar)
+                    .add(label)
+                    .add(
+                        AnimateTransform::new()
+                            .set("attributeName", "transform")
+                            .set("type", "translate")
+                            .set("values", positions)
+                            .set("keyTimes", position_times)
+                            .set("dur", dur.as_str())
+                            .set("repeatCount", "indefinite"),
+                    ),
+            )
+        });
+
+    // grows with the steps done and stays full during the pause
+    let progress_width = width - 2. * MARGIN;
+    let progress = Rectangle::new()
+        .set("x", MARGIN)
+        .set("y", baseline + 2.5 * FONT_SIZE)
+        .set("width", 0)
+        .set("height", 4)
+        .set("fill", IDLE)
+        .add(
+            Animate::new()
+                .set("attributeName", "width")
+                .set("values", format!("0;0;{0};{0}", progress_width))
+                .set(
+                    "keyTimes",
+                    format!(
+                        "0;{:.5};{:.5};1",
+                        PAUSE_BEFORE as f64 * step_seconds / duration,
+                        end / duration
+                    ),
+                )
+                .set("dur", dur.as_str())
+                .set("repeatCount", "indefinite"),
+        );
+
+    Document::new()
+        .set("viewBox", (0, 0, width, height))
+        .set("width", width)
+        .set("height", height)
+        .add(
+            Rectangle::new()
+                .set("width", "100%")
+                .set("height", "100%")
+                .set("fill", "white"),
+        )
+        .add(text(MARGIN, MARGIN, "start", title).set("font-size", FONT_SIZE * 1.3))
+        .add(text(MARGIN, MARGIN + 1.6 * FONT_SIZE, "start", caption))
+        .add(legend)
+        .add(bars)
+        .add(progress)
+}
+
+/// Renders the sort of `values` recorded in `steps` like [`sort_animation`] and saves it as SVG to
+/// `path`.
+pub fn draw_sort_animation<P: AsRef<Path>>(
+    title: &str,
+    values: &[i32],
+    steps: &[Step],
+    step_seconds: f64,
+    path: P,
+) -> io::Result<()> {
+    svg::save(path, &sort_animation(title, values, steps, step_seconds))
+}
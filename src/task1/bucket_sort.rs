@@ -0,0 +1,78 @@
+//! Bucket sort for integers.
+
+use crate::task1::insertion_sort::insertion_sort_probed;
+use crate::task1::merge_sort::apply_permutation;
+use crate::task1::probe::Probe;
+
+/// Sorts the slice by distributing the values into as many equal-width buckets between the minimum
+/// and the maximum as there are elements, sorting every bucket with insertion sort and concatenating
+/// the buckets.
+///
+/// Runs in O(n) expected time for uniformly distributed values and O(n²) when most values fall into
+/// one bucket. Allocates the buckets, O(n) in total.
+pub fn bucket_sort(slice: &mut [i32]) {
+    bucket_sort_probed(slice, &mut ());
+}
+
+/// Same as [`bucket_sort`], reporting the writes and the swaps sorting the buckets to `probe`.
+pub fn bucket_sort_probed(slice: &mut [i32], probe: &mut impl Probe) {
+    sort(slice, |&value| value, probe);
+}
+
+/// Same as [`bucket_sort`] for any elements, ordered by the integer key `key` extracts. Pairs of
+/// the keys and the indices are sorted and the elements moved to their positions at the end, so
+/// the sort is stable.
+pub fn bucket_sort_by_key<T, F: FnMut(&T) -> i32>(slice: &mut [T], key: F) {
+    let mut pairs = slice
+        .iter()
+        .map(key)
+        .zip(0..)
+        .collect::<Vec<(i32, usize)>>();
+    sort(&mut pairs, |&(key, _)| key, &mut ());
+    let mut order = pairs.iter().map(|&(_, i)| i).collect::<Vec<usize>>();
+    apply_permutation(slice, &mut order, &mut ());
+}
+
+fn sort<T: Copy>(slice: &mut [T], key: impl Fn(&T) -> i32, probe: &mut impl Probe) {
+    let (min, max) = match (slice.iter().map(&key).min(), slice.iter().map(&key).max()) {
+        (Some(min), Some(max)) => (min as i64, max as i64),
+        _ => return,
+    };
+    let count = slice.len();
+    let width = (max - min + 1) as u128;
+    let mut buckets = vec![vec![]; count];
+    for &value in slice.iter() {
+        let bucket = ((key(&value) as i64 - min) as u128 * count as u128 / width) as usize;
+        buckets[bucket].push(value);
+    }
+
+    let mut position = 0;
+    for bucket in buckets.iter_mut() {
+        insertion_sort_probed(bucket, |a, b| key(a).cmp(&key(b)), probe);
+        slice[position..position + bucket.len()].copy_from_slice(bucket);
+        position += bucket.len();
+    }
+    // into the buckets and back
+    probe.writes(2 * count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending, check_stable};
+
+    #[test]
+    fn sorts_like_std() {
+        check_sort(bucket_sort);
+    }
+
+    #[test]
+    fn sort_by_key_is_stable() {
+        check_stable(|slice| bucket_sort_by_key(slice, |tagged| tagged.key));
+    }
+
+    #[test]
+    fn sorts_by_key() {
+        check_sort_descending(|slice| bucket_sort_by_key(slice, |value| !*value));
+    }
+}
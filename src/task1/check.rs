@@ -0,0 +1,94 @@
+//! Checks of the sorts against [`slice::sort`], shared by the tests of the sort modules.
+
+use crate::task1::stability::{is_stable, Tagged};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Inputs the sorts get wrong most easily: empty, one element, all equal, sorted, reversed and
+/// organ-pipe arrays, the extremes of `i32` and random arrays with lengths around the thresholds of
+/// the sorts, with distinct values and with many duplicates.
+pub(crate) fn cases() -> Vec<Vec<i32>> {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut cases = vec![
+        vec![],
+        vec![42],
+        vec![7; 100],
+        (0..100).collect(),
+        (0..100).rev().collect(),
+        (0..50).chain((0..50).rev()).collect(),
+        vec![i32::MAX, i32::MIN, 0, -1, 1, i32::MIN, i32::MAX],
+        vec![i32::MAX, i32::MAX - 2, i32::MAX - 1, i32::MAX],
+        vec![i32::MIN + 2, i32::MIN, i32::MIN + 1, i32::MIN],
+    ];
+    for len in [2, 3, 15, 16, 17, 31, 32, 33, 100, 1000] {
+        cases.push((0..len).map(|_| rng.gen()).collect());
+        cases.push((0..len).map(|_| rng.gen_range(-5..5)).collect());
+    }
+    cases
+}
+
+/// Sorts every case with `sort` and checks the result against [`slice::sort`].
+pub(crate) fn check_sort(sort: impl Fn(&mut [i32])) {
+    check_sort_within(i64::MAX, sort);
+}
+
+/// Same as [`check_sort`], skipping the cases spanning more than `range` values.
+pub(crate) fn check_sort_within(range: i64, sort: impl Fn(&mut [i32])) {
+    for case in cases()
+        .into_iter()
+        .filter(|case| value_range(case) <= range)
+    {
+        let mut sorted = case.clone();
+        sort(&mut sorted);
+        let mut expected = case.clone();
+        expected.sort();
+        assert_eq!(sorted, expected, "sorting {:?}", case);
+    }
+}
+
+/// Sorts every case with `sort` and checks the result against [`slice::sort`] in descending
+/// order, for the `_by` variants given a reversed comparator and the `_by_key` variants given the
+/// key `!value`, which reverses the order of `i32` without overflowing.
+pub(crate) fn check_sort_descending(sort: impl Fn(&mut [i32])) {
+    check_sort_descending_within(i64::MAX, sort);
+}
+
+/// Same as [`check_sort_descending`], skipping the cases spanning more than `range` values.
+pub(crate) fn check_sort_descending_within(range: i64, sort: impl Fn(&mut [i32])) {
+    for case in cases()
+        .into_iter()
+        .filter(|case| value_range(case) <= range)
+    {
+        let mut sorted = case.clone();
+        sort(&mut sorted);
+        let mut expected = case.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+        assert_eq!(sorted, expected, "sorting {:?} in descending order", case);
+    }
+}
+
+/// Sorts keys with many duplicates tagged with their indices and checks with [`is_stable`] that
+/// `sort` keeps the order of equal keys.
+pub(crate) fn check_stable(sort: impl Fn(&mut [Tagged<i32>])) {
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut cases = vec![
+        vec![7; 100],
+        (0..100).map(|i| i / 10).collect(),
+        (0..100).rev().map(|i| i / 10).collect(),
+        vec![i32::MAX, i32::MAX - 1, i32::MAX, i32::MAX - 1, i32::MAX],
+        vec![i32::MIN + 1, i32::MIN, i32::MIN + 1, i32::MIN, i32::MIN],
+    ];
+    for len in [2, 3, 17, 33, 100, 1000] {
+        cases.push((0..len).map(|_| rng.gen_range(-5..5)).collect());
+    }
+    for case in cases {
+        assert!(is_stable(&case, &sort), "sorting {:?}", case);
+    }
+}
+
+fn value_range(slice: &[i32]) -> i64 {
+    match (slice.iter().min(), slice.iter().max()) {
+        (Some(&min), Some(&max)) => max as i64 - min as i64 + 1,
+        _ => 0,
+    }
+}
@@ -0,0 +1,167 @@
+//! Operation counters for instrumented runs of the sorts.
+//!
+//! The `_probed` variants of the sorts report their swaps and the elements they write outside of
+//! swaps to per-thread counters through the [`Counting`] probe. Comparisons are counted by sorting
+//! [`Counted`] elements, so only the comparison sorts report them.
+
+use crate::task1::probe::Probe;
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+/// Operations performed by a sort.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Operations {
+    /// Comparisons between [`Counted`] elements.
+    pub comparisons: u64,
+    /// Swaps of two elements.
+    pub swaps: u64,
+    /// Elements written to the slice or to a buffer outside of swaps.
+    pub writes: u64,
+}
+
+thread_local! {
+    static OPERATIONS: Cell<Operations> = const {
+        Cell::new(Operations {
+            comparisons: 0,
+            swaps: 0,
+            writes: 0,
+        })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Operations)) {
+    OPERATIONS.with(|operations| {
+        let mut current = operations.get();
+        f(&mut current);
+        operations.set(current);
+    });
+}
+
+/// Runs `f` and returns the comparisons of [`Counted`] elements and the moves reported to
+/// [`Counting`] on the current thread.
+pub fn count_operations(f: impl FnOnce()) -> Operations {
+    let outer = OPERATIONS.with(|operations| operations.replace(Operations::default()));
+    f();
+    let counted = OPERATIONS.with(|operations| operations.get());
+    // nested counts are added to the enclosing ones
+    OPERATIONS.with(|operations| {
+        operations.set(Operations {
+            comparisons: outer.comparisons + counted.comparisons,
+            swaps: outer.swaps + counted.swaps,
+            writes: outer.writes + counted.writes,
+        })
+    });
+    counted
+}
+
+/// Element wrapper counting every comparison in [`count_operations`].
+#[derive(Debug, Clone, Copy)]
+pub struct Counted<T>(pub T);
+
+impl<T: PartialEq> PartialEq for Counted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        update(|operations| operations.comparisons += 1);
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq> Eq for Counted<T> {}
+
+impl<T: PartialOrd> PartialOrd for Counted<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        update(|operations| operations.comparisons += 1);
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T: Ord> Ord for Counted<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        update(|operations| operations.comparisons += 1);
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Probe counting the swaps and writes of a sort in [`count_operations`], a rotation counts as
+/// writes of the rotated elements.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Counting;
+
+impl Probe for Counting {
+    fn swap<T>(&mut self, _slice: &[T], _a: usize, _b: usize) {
+        update(|operations| operations.swaps += 1);
+    }
+
+    fn rotate<T>(&mut self, slice: &[T]) {
+        self.writes(slice.len());
+    }
+
+    fn writes(&mut self, count: usize) {
+        update(|operations| operations.writes += count as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::insertion_sort::{binary_insertion_sort_probed, insertion_sort_probed};
+
+    fn counted(values: &[i32]) -> Vec<Counted<i32>> {
+        values.iter().copied().map(Counted).collect()
+    }
+
+    #[test]
+    fn counts_insertion_sort_by_hand() {
+        // 2 < 3: swap to [2, 3, 1]; 1 < 3: swap to [2, 1, 3]; 1 < 2: swap to [1, 2, 3]
+        let mut slice = counted(&[3, 2, 1]);
+        let operations =
+            count_operations(|| insertion_sort_probed(&mut slice, Counted::cmp, &mut Counting));
+        let expected = Operations {
+            comparisons: 3,
+            swaps: 3,
+            writes: 0,
+        };
+        assert_eq!(operations, expected);
+        assert_eq!(
+            slice.iter().map(|value| value.0).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+
+        // the sorted input takes one comparison per element after the first
+        let mut slice = counted(&[1, 2, 3]);
+        let operations =
+            count_operations(|| insertion_sort_probed(&mut slice, Counted::cmp, &mut Counting));
+        assert_eq!(operations.comparisons, 2);
+        assert_eq!(operations.swaps, 0);
+    }
+
+    #[test]
+    fn counts_rotations_as_writes() {
+        // 2 goes before 3 rotating two elements, 1 before both rotating three
+        let mut slice = counted(&[3, 2, 1]);
+        let operations = count_operations(|| {
+            binary_insertion_sort_probed(&mut slice, Counted::cmp, &mut Counting)
+        });
+        assert_eq!((operations.swaps, operations.writes), (0, 5));
+    }
+
+    #[test]
+    fn adds_nested_counts_to_the_enclosing_ones() {
+        let mut inner = Operations::default();
+        let outer = count_operations(|| {
+            let _ = Counted(1) < Counted(2);
+            inner = count_operations(|| {
+                let _ = Counted(1) == Counted(2);
+                Counting.swap(&[0], 0, 0);
+            });
+            Counting.writes(4);
+        });
+        let expected = |comparisons, swaps, writes| Operations {
+            comparisons,
+            swaps,
+            writes,
+        };
+        assert_eq!(inner, expected(1, 1, 0));
+        assert_eq!(outer, expected(2, 1, 4));
+        assert_eq!(count_operations(|| ()), Operations::default());
+    }
+}
@@ -0,0 +1,85 @@
+//! Counting sort for integers.
+
+use crate::task1::merge_sort::apply_permutation;
+use crate::task1::probe::Probe;
+
+/// Sorts the slice in place by counting the occurrences of every value between the minimum and the
+/// maximum and writing the values back in order.
+///
+/// Runs in O(n + k) time and allocates k counters, where k = max - min + 1, so it only pays off for
+/// narrow value ranges. The counters alone take 32 GiB for the full `i32` range.
+pub fn counting_sort(slice: &mut [i32]) {
+    counting_sort_probed(slice, &mut ());
+}
+
+/// Same as [`counting_sort`], reporting the writes to `probe`.
+pub fn counting_sort_probed(slice: &mut [i32], probe: &mut impl Probe) {
+    let (min, max) = match (slice.iter().min(), slice.iter().max()) {
+        (Some(&min), Some(&max)) => (min, max),
+        _ => return,
+    };
+    let mut counts = vec![0usize; (max as i64 - min as i64) as usize + 1];
+    for &value in slice.iter() {
+        counts[(value as i64 - min as i64) as usize] += 1;
+    }
+
+    let mut position = 0;
+    for (offset, &count) in counts.iter().enumerate() {
+        let value = (min as i64 + offset as i64) as i32;
+        slice[position..position + count].fill(value);
+        probe.writes(count);
+        position += count;
+    }
+}
+
+/// Sorts the slice in place by the integer key `key` extracts: the elements are counted per key,
+/// the counts give the position of every element and the elements are moved there at the end.
+///
+/// The sort is stable. Runs in O(n + k) time like [`counting_sort`] and also allocates the keys and
+/// a permutation of the slice length.
+pub fn counting_sort_by_key<T, F: FnMut(&T) -> i32>(slice: &mut [T], key: F) {
+    let keys = slice.iter().map(key).collect::<Vec<i32>>();
+    let (min, max) = match (keys.iter().min(), keys.iter().max()) {
+        (Some(&min), Some(&max)) => (min as i64, max as i64),
+        _ => return,
+    };
+    let mut starts = vec![0usize; (max - min) as usize + 1];
+    for &key in &keys {
+        starts[(key as i64 - min) as usize] += 1;
+    }
+    let mut total = 0;
+    for start in starts.iter_mut() {
+        (total, *start) = (total + *start, total);
+    }
+    let mut order = vec![0; keys.len()];
+    for (i, &key) in keys.iter().enumerate() {
+        let start = &mut starts[(key as i64 - min) as usize];
+        order[*start] = i;
+        *start += 1;
+    }
+    apply_permutation(slice, &mut order, &mut ());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort_descending_within, check_sort_within, check_stable};
+
+    #[test]
+    fn sorts_like_std() {
+        // the counters of wider ranges do not fit into the memory
+        check_sort_within(1 << 20, counting_sort);
+    }
+
+    #[test]
+    fn sort_by_key_is_stable() {
+        check_stable(|slice| counting_sort_by_key(slice, |tagged| tagged.key));
+    }
+
+    #[test]
+    fn sorts_by_key() {
+        check_sort_descending_within(1 << 20, |slice| {
+            counting_sort_by_key(slice, |value| !*value)
+        });
+    }
+}
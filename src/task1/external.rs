@@ -0,0 +1,562 @@
+//! External merge sort of integer files larger than the memory.
+//!
+//! The input is read in runs that fit into the memory budget, every run is sorted in memory and
+//! spilled to a temporary file, and the runs are merged with a binary heap into the output file,
+//! up to [`ExternalSort::fan_in`] runs at a time. While there are more runs than that, groups of
+//! them are merged into longer runs first.
+
+use crate::task1::introsort;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Smallest memory budget in bytes, smaller budgets are raised to it.
+pub const MIN_MEMORY: usize = 4096;
+
+/// Most runs merged at once, every merged run keeps a file open.
+pub const MAX_FAN_IN: usize = 64;
+
+// buffer of the input while the runs are formed, of a run while it is written and the share of the
+// budget a run needs while merging
+const IO_BUFFER: usize = 8192;
+
+type RunSort = Box<dyn Fn(&mut [i32])>;
+
+/// Layout of the integers in a file sorted by [`ExternalSort`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// Decimal integers separated by whitespace or commas, written one per line.
+    Text,
+    /// Little-endian `i32` values without separators.
+    Binary,
+}
+
+impl FileFormat {
+    /// Every file format.
+    pub const ALL: [FileFormat; 2] = [FileFormat::Text, FileFormat::Binary];
+
+    /// Name of the format, as accepted by [`str::parse`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            FileFormat::Text => "text",
+            FileFormat::Binary => "binary",
+        }
+    }
+
+    /// Binary for files with the extension `bin`, text otherwise.
+    pub fn of_path(path: &Path) -> FileFormat {
+        match path.extension().is_some_and(|extension| extension == "bin") {
+            true => FileFormat::Binary,
+            false => FileFormat::Text,
+        }
+    }
+}
+
+impl fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Error returned when parsing an unknown file format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFileFormatError;
+
+impl fmt::Display for UnknownFileFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown file format")
+    }
+}
+
+impl std::error::Error for UnknownFileFormatError {}
+
+impl FromStr for FileFormat {
+    type Err = UnknownFileFormatError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        FileFormat::ALL
+            .iter()
+            .copied()
+            .find(|format| format.name() == value)
+            .ok_or(UnknownFileFormatError)
+    }
+}
+
+/// Reads every value of a file in `format`, e.g. to sort it in memory.
+///
+/// Fails like [`ExternalSort::sort_file`] on invalid input.
+pub fn read_values(reader: impl BufRead, format: FileFormat) -> io::Result<Vec<i32>> {
+    let mut reader = Values::new(reader, format);
+    let mut values = vec![];
+    while let Some(value) = reader.next()? {
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Writes the values in `format`, so [`read_values`] reads them back.
+pub fn write_values(writer: &mut impl Write, format: FileFormat, values: &[i32]) -> io::Result<()> {
+    for &value in values {
+        write_value(writer, format, value)?;
+    }
+    writer.flush()
+}
+
+/// What [`ExternalSort::sort_file`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExternalStats {
+    /// Integers in the input.
+    pub values: u64,
+    /// Sorted runs spilled to temporary files.
+    pub runs: usize,
+    /// Merge passes over the data, the last one writes the output.
+    pub passes: usize,
+    /// Time spent reading, sorting and spilling the runs.
+    pub run_time: Duration,
+    /// Time spent merging the runs.
+    pub merge_time: Duration,
+}
+
+/// Sorts files of `i32` values with a bounded memory budget.
+///
+/// The budget bounds the values held in memory: a run holds `memory / 4` values, while merging the
+/// budget is split into the buffers of the merged runs and of the output. The input and every
+/// spilled run additionally use an I/O buffer of 8 KiB.
+pub struct ExternalSort {
+    memory: usize,
+    input_format: FileFormat,
+    output_format: FileFormat,
+    temp_dir: PathBuf,
+    sort: RunSort,
+}
+
+impl ExternalSort {
+    /// External sort using `memory` bytes, reading and writing text and sorting the runs with
+    /// [`introsort()`]. The runs are spilled to [`std::env::temp_dir`].
+    pub fn new(memory: usize) -> Self {
+        ExternalSort {
+            memory: memory.max(MIN_MEMORY),
+            input_format: FileFormat::Text,
+            output_format: FileFormat::Text,
+            temp_dir: std::env::temp_dir(),
+            sort: Box::new(introsort),
+        }
+    }
+
+    /// Sets the formats of the input and the output file.
+    pub fn with_formats(mut self, input: FileFormat, output: FileFormat) -> Self {
+        self.input_format = input;
+        self.output_format = output;
+        self
+    }
+
+    /// Sets the directory the runs are spilled to.
+    pub fn with_temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = dir.into();
+        self
+    }
+
+    /// Sets the sort of the runs, any in-place sort of `i32` slices.
+    pub fn with_sort(mut self, sort: impl Fn(&mut [i32]) + 'static) -> Self {
+        self.sort = Box::new(sort);
+        self
+    }
+
+    /// Memory budget in bytes.
+    pub fn memory(&self) -> usize {
+        self.memory
+    }
+
+    /// Values per run.
+    pub fn run_length(&self) -> usize {
+        self.memory / size_of::<i32>()
+    }
+
+    /// Runs merged at once: one per 8 KiB of the budget, less one for the output, between 2 and
+    /// [`MAX_FAN_IN`].
+    pub fn fan_in(&self) -> usize {
+        (self.memory / IO_BUFFER)
+            .saturating_sub(1)
+            .clamp(2, MAX_FAN_IN)
+    }
+
+    /// Sorts the integers of `input` into `output`, which is overwritten.
+    ///
+    /// Fails on I/O errors and with [`io::ErrorKind::InvalidData`] if the input is not a valid text
+    /// or binary file of `i32` values. The spilled runs are removed, also on failure.
+    pub fn sort_file(&self, input: &Path, output: &Path) -> io::Result<ExternalStats> {
+        let start = Instant::now();
+        let file = BufReader::with_capacity(IO_BUFFER, File::open(input)?);
+        let mut reader = Values::new(file, self.input_format);
+        let run_length = self.run_length();
+        let mut buffer = Vec::with_capacity(run_length);
+        let mut runs = vec![];
+        let mut values = 0;
+        loop {
+            buffer.clear();
+            while buffer.len() < run_length {
+                match reader.next()? {
+                    Some(value) => buffer.push(value),
+                    None => break,
+                }
+            }
+            if buffer.is_empty() {
+                break;
+            }
+            values += buffer.len() as u64;
+            (self.sort)(&mut buffer);
+            let (run, mut writer) = Run::create(&self.temp_dir, IO_BUFFER)?;
+            write_values(&mut writer, FileFormat::Binary, &buffer)?;
+            runs.push(run);
+            if buffer.len() < run_length {
+                break;
+            }
+        }
+        drop(buffer);
+        let spilled = runs.len();
+        let run_time = start.elapsed();
+
+        let start = Instant::now();
+        let merge_buffer = self.memory / (self.fan_in() + 1);
+        let mut passes = 0;
+        while runs.len() > self.fan_in() {
+            let mut merged = vec![];
+            for group in runs.chunks(self.fan_in()) {
+                let (run, mut writer) = Run::create(&self.temp_dir, merge_buffer)?;
+                merge(group, merge_buffer, &mut writer, FileFormat::Binary)?;
+                writer.flush()?;
+                merged.push(run);
+            }
+            runs = merged;
+            passes += 1;
+        }
+        let mut writer = BufWriter::with_capacity(merge_buffer, File::create(output)?);
+        merge(&runs, merge_buffer, &mut writer, self.output_format)?;
+        writer.flush()?;
+        if !runs.is_empty() {
+            passes += 1;
+        }
+        Ok(ExternalStats {
+            values,
+            runs: spilled,
+            passes,
+            run_time,
+            merge_time: start.elapsed(),
+        })
+    }
+}
+
+// merges the sorted runs into `writer`, always taking the smallest head from a heap
+fn merge(
+    runs: &[Run],
+    capacity: usize,
+    writer: &mut impl Write,
+    format: FileFormat,
+) -> io::Result<()> {
+    let mut readers = runs
+        .iter()
+        .map(|run| {
+            let file = BufReader::with_capacity(capacity, File::open(&run.path)?);
+            Ok(Values::new(file, FileFormat::Binary))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut heap = BinaryHeap::with_capacity(readers.len());
+    for (i, reader) in readers.iter_mut().enumerate() {
+        if let Some(value) = reader.next()? {
+            heap.push(Reverse((value, i)));
+        }
+    }
+    while let Some(Reverse((value, i))) = heap.pop() {
+        write_value(writer, format, value)?;
+        if let Some(next) = readers[i].next()? {
+            heap.push(Reverse((next, i)));
+        }
+    }
+    Ok(())
+}
+
+fn write_value(writer: &mut impl Write, format: FileFormat, value: i32) -> io::Result<()> {
+    match format {
+        FileFormat::Text => writeln!(writer, "{}", value),
+        FileFormat::Binary => writer.write_all(&value.to_le_bytes()),
+    }
+}
+
+fn is_separator(byte: &u8) -> bool {
+    byte.is_ascii_whitespace() || *byte == b','
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// temporary file of a sorted run in binary format, removed when dropped
+struct Run {
+    path: PathBuf,
+}
+
+impl Run {
+    fn create(dir: &Path, capacity: usize) -> io::Result<(Run, BufWriter<File>)> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "course_project_run_{}_{}.bin",
+            process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = dir.join(name);
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        Ok((Run { path }, BufWriter::with_capacity(capacity, file)))
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// reads the values of a file one at a time
+struct Values<R> {
+    reader: R,
+    format: FileFormat,
+    token: Vec<u8>,
+}
+
+impl<R: BufRead> Values<R> {
+    fn new(reader: R, format: FileFormat) -> Self {
+        Values {
+            reader,
+            format,
+            token: vec![],
+        }
+    }
+
+    fn next(&mut self) -> io::Result<Option<i32>> {
+        match self.format {
+            FileFormat::Text => self.next_text(),
+            FileFormat::Binary => self.next_binary(),
+        }
+    }
+
+    fn next_text(&mut self) -> io::Result<Option<i32>> {
+        self.token.clear();
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                break;
+            }
+            let skipped = match self.token.is_empty() {
+                true => buffer.iter().take_while(|byte| is_separator(byte)).count(),
+                false => 0,
+            };
+            let length = buffer[skipped..]
+                .iter()
+                .take_while(|byte| !is_separator(byte))
+                .count();
+            self.token
+                .extend_from_slice(&buffer[skipped..skipped + length]);
+            let ended = skipped + length < buffer.len();
+            self.reader.consume(skipped + length);
+            if ended && !self.token.is_empty() {
+                break;
+            }
+        }
+        if self.token.is_empty() {
+            return Ok(None);
+        }
+        let token = String::from_utf8_lossy(&self.token);
+        token
+            .parse::<i32>()
+            .map(Some)
+            .map_err(|_| invalid_data(format!("invalid integer '{}'", token)))
+    }
+
+    fn next_binary(&mut self) -> io::Result<Option<i32>> {
+        let mut bytes = [0; size_of::<i32>()];
+        let mut filled = 0;
+        while filled < bytes.len() {
+            match self.reader.read(&mut bytes[filled..]) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        match filled {
+            0 => Ok(None),
+            4 => Ok(Some(i32::from_le_bytes(bytes))),
+            _ => Err(invalid_data(format!(
+                "file ends within a value, {} trailing bytes",
+                filled
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::io::Cursor;
+
+    // directory of the files of one test, removed with everything in it when dropped
+    struct TestDir {
+        path: PathBuf,
+    }
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("course_project_{}_{}", name, process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            TestDir { path }
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    fn random_values(len: usize, seed: u64) -> Vec<i32> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..len)
+            .map(|i| match i % 3 {
+                0 => rng.gen_range(-10..10),
+                _ => rng.gen(),
+            })
+            .collect()
+    }
+
+    // sorts `values` with `sort` through files in `dir`, checks the output against `slice::sort`
+    // and that no spilled run is left behind
+    fn check_sort_file(sort: ExternalSort, dir: &TestDir, values: &[i32]) -> ExternalStats {
+        let runs = dir.path.join("runs");
+        fs::create_dir_all(&runs).unwrap();
+        let input = dir.path.join("input");
+        let output = dir.path.join("output");
+        let mut writer = BufWriter::new(File::create(&input).unwrap());
+        write_values(&mut writer, sort.input_format, values).unwrap();
+        drop(writer);
+
+        let stats = ExternalSort::new(sort.memory)
+            .with_formats(sort.input_format, sort.output_format)
+            .with_temp_dir(&runs)
+            .sort_file(&input, &output)
+            .unwrap();
+
+        let sorted = read_values(
+            BufReader::new(File::open(&output).unwrap()),
+            sort.output_format,
+        );
+        let mut expected = values.to_vec();
+        expected.sort();
+        assert_eq!(sorted.unwrap(), expected);
+        assert_eq!(stats.values, values.len() as u64);
+        assert_eq!(fs::read_dir(&runs).unwrap().count(), 0);
+        stats
+    }
+
+    #[test]
+    fn sorts_single_run() {
+        let dir = TestDir::new("external_single_run");
+        let sort = ExternalSort::new(1 << 20);
+        let stats = check_sort_file(sort, &dir, &random_values(1000, 1));
+        assert_eq!((stats.runs, stats.passes), (1, 1));
+    }
+
+    #[test]
+    fn merges_several_runs_in_one_pass() {
+        let dir = TestDir::new("external_several_runs");
+        let sort = ExternalSort::new(40960);
+        assert_eq!((sort.run_length(), sort.fan_in()), (10240, 4));
+        let stats = check_sort_file(sort, &dir, &random_values(30000, 2));
+        assert_eq!((stats.runs, stats.passes), (3, 1));
+    }
+
+    #[test]
+    fn merges_in_several_passes() {
+        let dir = TestDir::new("external_several_passes");
+        let sort = ExternalSort::new(MIN_MEMORY);
+        assert_eq!((sort.run_length(), sort.fan_in()), (1024, 2));
+        let stats = check_sort_file(sort, &dir, &random_values(5000, 3));
+        assert_eq!((stats.runs, stats.passes), (5, 3));
+    }
+
+    #[test]
+    fn sorts_empty_input() {
+        let dir = TestDir::new("external_empty");
+        for format in FileFormat::ALL {
+            let sort = ExternalSort::new(MIN_MEMORY).with_formats(format, format);
+            let stats = check_sort_file(sort, &dir, &[]);
+            assert_eq!((stats.runs, stats.passes), (0, 0));
+            assert_eq!(fs::metadata(dir.path.join("output")).unwrap().len(), 0);
+        }
+    }
+
+    #[test]
+    fn sorts_text_and_binary_files() {
+        let dir = TestDir::new("external_formats");
+        let values = random_values(3000, 4);
+        for input in FileFormat::ALL {
+            for output in FileFormat::ALL {
+                let sort = ExternalSort::new(MIN_MEMORY).with_formats(input, output);
+                check_sort_file(sort, &dir, &values);
+            }
+        }
+    }
+
+    #[test]
+    fn reads_numbers_split_across_the_buffer() {
+        let text = "12345, -678\n\n9 ,-2147483648\t2147483647,,0";
+        let reader = BufReader::with_capacity(3, Cursor::new(text));
+        assert_eq!(
+            read_values(reader, FileFormat::Text).unwrap(),
+            [12345, -678, 9, i32::MIN, i32::MAX, 0]
+        );
+
+        let values = random_values(100, 5);
+        let mut bytes = vec![];
+        write_values(&mut bytes, FileFormat::Binary, &values).unwrap();
+        let reader = BufReader::with_capacity(3, Cursor::new(bytes));
+        assert_eq!(read_values(reader, FileFormat::Binary).unwrap(), values);
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        let text = read_values(Cursor::new("1 2x 3"), FileFormat::Text).unwrap_err();
+        assert_eq!(text.kind(), io::ErrorKind::InvalidData);
+        let overflow = read_values(Cursor::new("2147483648"), FileFormat::Text).unwrap_err();
+        assert_eq!(overflow.kind(), io::ErrorKind::InvalidData);
+        let binary = read_values(Cursor::new([1, 0, 0, 0, 2]), FileFormat::Binary).unwrap_err();
+        assert_eq!(binary.kind(), io::ErrorKind::InvalidData);
+
+        let dir = TestDir::new("external_invalid");
+        let runs = dir.path.join("runs");
+        fs::create_dir_all(&runs).unwrap();
+        let input = dir.path.join("input");
+        let values = (0..3000).map(|i| i.to_string()).collect::<Vec<_>>();
+        fs::write(&input, values.join("\n") + "\nnan\n").unwrap();
+        let err = ExternalSort::new(MIN_MEMORY)
+            .with_temp_dir(&runs)
+            .sort_file(&input, &dir.path.join("output"))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs::read_dir(&runs).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,79 @@
+//! Heap sort.
+
+use crate::task1::probe::{swap, Probe};
+use std::cmp::Ordering;
+
+/// Sorts the slice in place by building a max-heap and repeatedly moving its root behind the heap.
+///
+/// The sort is not stable, performs O(n log n) comparisons and uses no extra memory.
+pub fn heap_sort<T: Ord>(slice: &mut [T]) {
+    heap_sort_by(slice, T::cmp);
+}
+
+/// Same as [`heap_sort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn heap_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    heap_sort_probed(slice, compare, &mut ());
+}
+
+/// Same as [`heap_sort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn heap_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    heap_sort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Same as [`heap_sort_by`], reporting the swaps to `probe`.
+pub fn heap_sort_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    let len = slice.len();
+    for root in (0..len / 2).rev() {
+        sift_down(slice, root, len, &mut compare, probe);
+    }
+    for end in (1..len).rev() {
+        swap(slice, 0, end, probe);
+        sift_down(slice, 0, end, &mut compare, probe);
+    }
+}
+
+// restores the heap property of `slice[..end]` for the subtree at `root`
+fn sift_down<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    mut root: usize,
+    end: usize,
+    compare: &mut F,
+    probe: &mut impl Probe,
+) {
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= end {
+            return;
+        }
+        if child + 1 < end && compare(&slice[child], &slice[child + 1]).is_lt() {
+            child += 1;
+        }
+        if compare(&slice[root], &slice[child]).is_ge() {
+            return;
+        }
+        swap(slice, root, child, probe);
+        root = child;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending};
+
+    #[test]
+    fn sorts_like_std() {
+        check_sort(heap_sort);
+    }
+
+    #[test]
+    fn sorts_by_comparator_and_key() {
+        check_sort_descending(|slice| heap_sort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| heap_sort_by_key(slice, |value| !*value));
+    }
+}
@@ -0,0 +1,99 @@
+//! Insertion sort and binary insertion sort.
+
+use crate::task1::probe::{rotate_right, swap, Probe};
+use std::cmp::Ordering;
+
+/// Sorts the slice in place by swapping every element back until it is not less than its
+/// predecessor.
+///
+/// The sort is stable and performs O(n²) comparisons, O(n) on sorted input.
+pub fn insertion_sort<T: Ord>(slice: &mut [T]) {
+    insertion_sort_by(slice, T::cmp);
+}
+
+/// Same as [`insertion_sort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn insertion_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    insertion_sort_probed(slice, compare, &mut ());
+}
+
+/// Same as [`insertion_sort_by`], reporting the swaps to `probe`.
+pub fn insertion_sort_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    for i in 1..slice.len() {
+        let mut j = i;
+        while j > 0 && compare(&slice[j], &slice[j - 1]).is_lt() {
+            swap(slice, j, j - 1, probe);
+            j -= 1;
+        }
+    }
+}
+
+/// Same as [`insertion_sort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn insertion_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    insertion_sort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Sorts the slice in place by finding the position of every element in the sorted prefix with
+/// binary search and rotating it there.
+///
+/// The sort is stable and performs O(n log n) comparisons but still O(n²) moves.
+pub fn binary_insertion_sort<T: Ord>(slice: &mut [T]) {
+    binary_insertion_sort_by(slice, T::cmp);
+}
+
+/// Same as [`binary_insertion_sort`], ordering the elements with `compare` like
+/// [`slice::sort_by`].
+pub fn binary_insertion_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    binary_insertion_sort_probed(slice, compare, &mut ());
+}
+
+/// Same as [`binary_insertion_sort_by`], reporting the rotations to `probe`.
+pub fn binary_insertion_sort_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    for i in 1..slice.len() {
+        let (sorted, rest) = slice.split_at(i);
+        let position = sorted.partition_point(|x| compare(x, &rest[0]).is_le());
+        rotate_right(&mut slice[position..=i], probe);
+    }
+}
+
+/// Same as [`binary_insertion_sort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn binary_insertion_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    binary_insertion_sort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending, check_stable};
+
+    #[test]
+    fn sorts_like_std() {
+        check_sort(insertion_sort);
+        check_sort(binary_insertion_sort);
+    }
+
+    #[test]
+    fn is_stable() {
+        check_stable(insertion_sort);
+        check_stable(binary_insertion_sort);
+    }
+
+    #[test]
+    fn sorts_by_comparator_and_key() {
+        check_sort_descending(|slice| insertion_sort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| insertion_sort_by_key(slice, |value| !*value));
+        check_sort_descending(|slice| binary_insertion_sort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| binary_insertion_sort_by_key(slice, |value| !*value));
+        check_stable(|slice| insertion_sort_by_key(slice, |tagged| tagged.key));
+        check_stable(|slice| binary_insertion_sort_by_key(slice, |tagged| tagged.key));
+    }
+}
@@ -0,0 +1,113 @@
+//! Introsort: quicksort with a recursion depth limit.
+
+use crate::task1::heap_sort::heap_sort_probed;
+use crate::task1::insertion_sort::insertion_sort_probed;
+use crate::task1::probe::Probe;
+use crate::task1::quicksort::{median_of_ends, partition_around, Partition};
+use std::cmp::Ordering;
+
+/// Slice length below which [`introsort`] switches to insertion sort.
+pub const DEFAULT_INSERTION_THRESHOLD: usize = 16;
+
+/// Sorts the slice in place with introsort, using [`DEFAULT_INSERTION_THRESHOLD`].
+///
+/// The sort is not stable. Same as [`introsort_with`] with the default threshold.
+pub fn introsort<T: Ord>(slice: &mut [T]) {
+    introsort_with(slice, DEFAULT_INSERTION_THRESHOLD);
+}
+
+/// Same as [`introsort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn introsort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    introsort_with_by(slice, DEFAULT_INSERTION_THRESHOLD, compare);
+}
+
+/// Same as [`introsort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn introsort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    introsort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Sorts the slice in place with quicksort, switching to heap sort once the recursion gets deeper
+/// than 2·log₂(n) and to insertion sort for partitions shorter than `insertion_threshold`.
+///
+/// The sort is not stable and performs O(n log n) comparisons on every input. It recurses only into
+/// the smaller partition, so the recursion depth is O(log n) and sorted or adversarial inputs cannot
+/// overflow the stack.
+pub fn introsort_with<T: Ord>(slice: &mut [T], insertion_threshold: usize) {
+    introsort_with_by(slice, insertion_threshold, T::cmp);
+}
+
+/// Same as [`introsort_with`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn introsort_with_by<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    insertion_threshold: usize,
+    compare: F,
+) {
+    introsort_with_probed(slice, insertion_threshold, compare, &mut ());
+}
+
+/// Same as [`introsort_with_by`], reporting the pivots and swaps to `probe`.
+pub fn introsort_with_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    insertion_threshold: usize,
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    let depth_limit = 2 * slice.len().max(1).ilog2();
+    sort(slice, depth_limit, insertion_threshold, &mut compare, probe);
+}
+
+/// Same as [`introsort_with`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn introsort_with_by_key<T, K: Ord, F: FnMut(&T) -> K>(
+    slice: &mut [T],
+    insertion_threshold: usize,
+    mut key: F,
+) {
+    introsort_with_by(slice, insertion_threshold, |a, b| key(a).cmp(&key(b)));
+}
+
+fn sort<T, F: FnMut(&T, &T) -> Ordering>(
+    mut slice: &mut [T],
+    mut depth_limit: u32,
+    insertion_threshold: usize,
+    compare: &mut F,
+    probe: &mut impl Probe,
+) {
+    while slice.len() > insertion_threshold.max(1) {
+        if depth_limit == 0 {
+            heap_sort_probed(slice, &mut *compare, probe);
+            return;
+        }
+        depth_limit -= 1;
+
+        let pivot_idx = median_of_ends(slice, compare);
+        let (smaller, larger) =
+            partition_around(slice, pivot_idx, Partition::ThreeWay, compare, probe);
+        sort(smaller, depth_limit, insertion_threshold, compare, probe);
+        slice = larger;
+    }
+    insertion_sort_probed(slice, compare, probe);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending};
+
+    #[test]
+    fn sorts_like_std() {
+        check_sort(introsort);
+        for threshold in [0, 1, 4, 64] {
+            check_sort(|slice| introsort_with(slice, threshold));
+        }
+    }
+
+    #[test]
+    fn sorts_by_comparator_and_key() {
+        check_sort_descending(|slice| introsort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| introsort_by_key(slice, |value| !*value));
+        check_sort_descending(|slice| introsort_with_by(slice, 4, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| introsort_with_by_key(slice, 4, |value| !*value));
+    }
+}
@@ -0,0 +1,167 @@
+//! Top-down and bottom-up merge sort.
+//!
+//! Both variants merge a permutation of indices instead of the elements themselves, so the elements
+//! never have to be cloned; the sorted permutation is applied to the slice at the end.
+
+use crate::task1::probe::{swap, Probe};
+use std::cmp::Ordering;
+
+/// Sorts the slice in place with recursive (top-down) merge sort.
+///
+/// The sort is stable and performs O(n log n) comparisons, it allocates two index buffers of the
+/// slice length.
+pub fn merge_sort<T: Ord>(slice: &mut [T]) {
+    merge_sort_by(slice, T::cmp);
+}
+
+/// Same as [`merge_sort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn merge_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    merge_sort_probed(slice, compare, &mut ());
+}
+
+/// Same as [`merge_sort_by`], reporting the swaps applying the permutation to `probe`.
+pub fn merge_sort_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    let mut order = (0..slice.len()).collect::<Vec<usize>>();
+    let mut buffer = order.clone();
+    sort_top_down(slice, &mut order, &mut buffer, &mut compare);
+    apply_permutation(slice, &mut order, probe);
+}
+
+/// Same as [`merge_sort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn merge_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    merge_sort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Sorts the slice in place with iterative (bottom-up) merge sort, merging runs of 1, 2, 4, ...
+/// elements.
+///
+/// The sort is stable and performs O(n log n) comparisons, it allocates two index buffers of the
+/// slice length.
+pub fn bottom_up_merge_sort<T: Ord>(slice: &mut [T]) {
+    bottom_up_merge_sort_by(slice, T::cmp);
+}
+
+/// Same as [`bottom_up_merge_sort`], ordering the elements with `compare` like
+/// [`slice::sort_by`].
+pub fn bottom_up_merge_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    bottom_up_merge_sort_probed(slice, compare, &mut ());
+}
+
+/// Same as [`bottom_up_merge_sort_by`], reporting the swaps applying the permutation to `probe`.
+pub fn bottom_up_merge_sort_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    let len = slice.len();
+    let mut order = (0..len).collect::<Vec<usize>>();
+    let mut buffer = order.clone();
+    let mut width = 1;
+    while width < len {
+        for start in (0..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+            merge(
+                slice,
+                &mut order[start..end],
+                mid - start,
+                &mut buffer[start..end],
+                &mut compare,
+            );
+        }
+        width *= 2;
+    }
+    apply_permutation(slice, &mut order, probe);
+}
+
+/// Same as [`bottom_up_merge_sort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn bottom_up_merge_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    bottom_up_merge_sort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(super) fn sort_top_down<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &[T],
+    order: &mut [usize],
+    buffer: &mut [usize],
+    compare: &mut F,
+) {
+    if order.len() <= 1 {
+        return;
+    }
+    let mid = order.len() / 2;
+    sort_top_down(slice, &mut order[..mid], &mut buffer[..mid], compare);
+    sort_top_down(slice, &mut order[mid..], &mut buffer[mid..], compare);
+    merge(slice, order, mid, buffer, compare);
+}
+
+// merges the sorted runs `order[..mid]` and `order[mid..]`, taking from the left run on ties
+pub(super) fn merge<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &[T],
+    order: &mut [usize],
+    mid: usize,
+    buffer: &mut [usize],
+    compare: &mut F,
+) {
+    let (mut left, mut right) = (0, mid);
+    for target in buffer.iter_mut() {
+        if right >= order.len()
+            || (left < mid && compare(&slice[order[left]], &slice[order[right]]).is_le())
+        {
+            *target = order[left];
+            left += 1;
+        } else {
+            *target = order[right];
+            right += 1;
+        }
+    }
+    order.copy_from_slice(buffer);
+}
+
+// moves `slice[order[k]]` to position `k` by following the cycles of the permutation,
+// `order` is left as the identity
+pub(super) fn apply_permutation<T>(slice: &mut [T], order: &mut [usize], probe: &mut impl Probe) {
+    for start in 0..order.len() {
+        let mut current = start;
+        while order[current] != start {
+            let next = order[current];
+            swap(slice, current, next, probe);
+            order[current] = current;
+            current = next;
+        }
+        order[current] = current;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending, check_stable};
+
+    #[test]
+    fn sorts_like_std() {
+        check_sort(merge_sort);
+        check_sort(bottom_up_merge_sort);
+    }
+
+    #[test]
+    fn is_stable() {
+        check_stable(merge_sort);
+        check_stable(bottom_up_merge_sort);
+    }
+
+    #[test]
+    fn sorts_by_comparator_and_key() {
+        check_sort_descending(|slice| merge_sort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| merge_sort_by_key(slice, |value| !*value));
+        check_sort_descending(|slice| bottom_up_merge_sort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| bottom_up_merge_sort_by_key(slice, |value| !*value));
+        check_stable(|slice| merge_sort_by_key(slice, |tagged| tagged.key));
+        check_stable(|slice| bottom_up_merge_sort_by_key(slice, |tagged| tagged.key));
+    }
+}
@@ -0,0 +1,226 @@
+//! Parallel merge sort and quicksort on scoped threads.
+//!
+//! Both sorts split the work recursively: every split hands one half to a new thread together with
+//! half of the remaining threads, until one thread is left or a part is shorter than the cutoff, and
+//! the part is then sorted sequentially. They have no `_probed` variants, the operations in the
+//! spawned threads could not be seen by [`count_operations`](crate::task1::count_operations).
+
+use crate::task1::merge_sort::{apply_permutation, merge, sort_top_down};
+use crate::task1::quicksort::{median_of_ends, partition_around, sort, Partition};
+use std::cmp::Ordering;
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// Part length below which the parallel sorts sort sequentially unless given.
+pub const DEFAULT_CUTOFF: usize = 1 << 13;
+
+/// Threads of the parallel sorts unless given: the available parallelism of the machine.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Sorts the slice in place with merge sort on [`default_threads`] threads.
+///
+/// Same as [`parallel_merge_sort_with`] with [`DEFAULT_CUTOFF`].
+pub fn parallel_merge_sort<T: Ord + Sync>(slice: &mut [T]) {
+    parallel_merge_sort_with(slice, default_threads(), DEFAULT_CUTOFF);
+}
+
+/// Same as [`parallel_merge_sort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn parallel_merge_sort_by<T: Sync, F: Fn(&T, &T) -> Ordering + Sync>(
+    slice: &mut [T],
+    compare: F,
+) {
+    parallel_merge_sort_with_by(slice, default_threads(), DEFAULT_CUTOFF, compare);
+}
+
+/// Same as [`parallel_merge_sort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn parallel_merge_sort_by_key<T: Sync, K: Ord, F: Fn(&T) -> K + Sync>(slice: &mut [T], key: F) {
+    parallel_merge_sort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Sorts the slice in place with merge sort on up to `threads` threads, parts shorter than `cutoff`
+/// are sorted by one thread.
+///
+/// Like [`merge_sort`](crate::task1::merge_sort()) it sorts a permutation of indices and applies it
+/// at the end, so the sort is stable. The merges of the parts and the permutation are sequential.
+pub fn parallel_merge_sort_with<T: Ord + Sync>(slice: &mut [T], threads: usize, cutoff: usize) {
+    parallel_merge_sort_with_by(slice, threads, cutoff, T::cmp);
+}
+
+/// Same as [`parallel_merge_sort_with`], ordering the elements with `compare` like
+/// [`slice::sort_by`].
+pub fn parallel_merge_sort_with_by<T: Sync, F: Fn(&T, &T) -> Ordering + Sync>(
+    slice: &mut [T],
+    threads: usize,
+    cutoff: usize,
+    compare: F,
+) {
+    let mut order = (0..slice.len()).collect::<Vec<usize>>();
+    let mut buffer = order.clone();
+    merge_sort_parts(slice, &mut order, &mut buffer, threads, cutoff, &compare);
+    apply_permutation(slice, &mut order, &mut ());
+}
+
+/// Same as [`parallel_merge_sort_with`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn parallel_merge_sort_with_by_key<T: Sync, K: Ord, F: Fn(&T) -> K + Sync>(
+    slice: &mut [T],
+    threads: usize,
+    cutoff: usize,
+    key: F,
+) {
+    parallel_merge_sort_with_by(slice, threads, cutoff, |a, b| key(a).cmp(&key(b)));
+}
+
+fn merge_sort_parts<T: Sync, F: Fn(&T, &T) -> Ordering + Sync>(
+    slice: &[T],
+    order: &mut [usize],
+    buffer: &mut [usize],
+    threads: usize,
+    cutoff: usize,
+    compare: &F,
+) {
+    if threads <= 1 || order.len() <= cutoff.max(1) {
+        sort_top_down(slice, order, buffer, &mut &*compare);
+        return;
+    }
+    let mid = order.len() / 2;
+    let (left, right) = order.split_at_mut(mid);
+    let (left_buffer, right_buffer) = buffer.split_at_mut(mid);
+    thread::scope(|scope| {
+        scope.spawn(|| merge_sort_parts(slice, left, left_buffer, threads / 2, cutoff, compare));
+        merge_sort_parts(
+            slice,
+            right,
+            right_buffer,
+            threads - threads / 2,
+            cutoff,
+            compare,
+        );
+    });
+    merge(slice, order, mid, buffer, &mut &*compare);
+}
+
+/// Sorts the slice in place with quicksort on [`default_threads`] threads.
+///
+/// Same as [`parallel_quicksort_with`] with [`DEFAULT_CUTOFF`].
+pub fn parallel_quicksort<T: Ord + Send>(slice: &mut [T]) {
+    parallel_quicksort_with(slice, default_threads(), DEFAULT_CUTOFF);
+}
+
+/// Same as [`parallel_quicksort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn parallel_quicksort_by<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(
+    slice: &mut [T],
+    compare: F,
+) {
+    parallel_quicksort_with_by(slice, default_threads(), DEFAULT_CUTOFF, compare);
+}
+
+/// Same as [`parallel_quicksort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn parallel_quicksort_by_key<T: Send, K: Ord, F: Fn(&T) -> K + Sync>(slice: &mut [T], key: F) {
+    parallel_quicksort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Sorts the slice in place with quicksort on up to `threads` threads, parts shorter than `cutoff`
+/// are sorted by one thread.
+///
+/// Every part is split by a three-way partition around the median of three, the two sides are
+/// sorted in parallel. The sort is not stable; the first partitions are sequential, so the speedup
+/// is well below the number of threads.
+pub fn parallel_quicksort_with<T: Ord + Send>(slice: &mut [T], threads: usize, cutoff: usize) {
+    parallel_quicksort_with_by(slice, threads, cutoff, T::cmp);
+}
+
+/// Same as [`parallel_quicksort_with`], ordering the elements with `compare` like
+/// [`slice::sort_by`].
+pub fn parallel_quicksort_with_by<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(
+    slice: &mut [T],
+    threads: usize,
+    cutoff: usize,
+    compare: F,
+) {
+    quicksort_parts(slice, threads, cutoff, &compare);
+}
+
+/// Same as [`parallel_quicksort_with`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn parallel_quicksort_with_by_key<T: Send, K: Ord, F: Fn(&T) -> K + Sync>(
+    slice: &mut [T],
+    threads: usize,
+    cutoff: usize,
+    key: F,
+) {
+    parallel_quicksort_with_by(slice, threads, cutoff, |a, b| key(a).cmp(&key(b)));
+}
+
+fn quicksort_parts<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(
+    slice: &mut [T],
+    threads: usize,
+    cutoff: usize,
+    compare: &F,
+) {
+    if threads <= 1 || slice.len() <= cutoff.max(1) {
+        sort(
+            slice,
+            &mut median_of_ends,
+            Partition::ThreeWay,
+            &mut &*compare,
+            &mut (),
+        );
+        return;
+    }
+    let pivot_idx = median_of_ends(slice, &mut &*compare);
+    let (smaller, larger) = partition_around(
+        slice,
+        pivot_idx,
+        Partition::ThreeWay,
+        &mut &*compare,
+        &mut (),
+    );
+    thread::scope(|scope| {
+        scope.spawn(|| quicksort_parts(smaller, threads / 2, cutoff, compare));
+        quicksort_parts(larger, threads - threads / 2, cutoff, compare);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending, check_stable};
+
+    #[test]
+    fn sorts_like_std() {
+        check_sort(parallel_merge_sort);
+        check_sort(parallel_quicksort);
+        for threads in [1, 2, 3, 8] {
+            check_sort(|slice| parallel_merge_sort_with(slice, threads, 8));
+            check_sort(|slice| parallel_quicksort_with(slice, threads, 8));
+        }
+    }
+
+    #[test]
+    fn parallel_merge_sort_is_stable() {
+        check_stable(parallel_merge_sort);
+        for threads in [2, 3, 8] {
+            check_stable(|slice| parallel_merge_sort_with(slice, threads, 8));
+        }
+    }
+
+    #[test]
+    fn sorts_by_comparator_and_key() {
+        check_sort_descending(|slice| parallel_merge_sort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| parallel_merge_sort_by_key(slice, |value| !*value));
+        check_sort_descending(|slice| parallel_quicksort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| parallel_quicksort_by_key(slice, |value| !*value));
+        check_sort_descending(|slice| parallel_merge_sort_with_by(slice, 3, 8, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| {
+            parallel_merge_sort_with_by_key(slice, 3, 8, |value| !*value)
+        });
+        check_sort_descending(|slice| parallel_quicksort_with_by(slice, 3, 8, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| parallel_quicksort_with_by_key(slice, 3, 8, |value| !*value));
+        check_stable(|slice| parallel_merge_sort_with_by_key(slice, 3, 8, |tagged| tagged.key));
+    }
+}
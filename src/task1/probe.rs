@@ -0,0 +1,37 @@
+//! Hooks through which the sorts report the moves of their elements to instrumented runs.
+//!
+//! The sorts have `_probed` variants taking a [`Probe`], e.g.
+//! [`heap_sort_probed`](crate::task1::heap_sort_probed). The other variants pass `()`, whose hooks
+//! do nothing and compile away, so the sorts only pay for the probe when one is given.
+
+/// Observer of the moves of a sort. Every hook does nothing unless overridden.
+pub trait Probe {
+    /// Called before the elements at `a` and `b` of the slice are swapped.
+    fn swap<T>(&mut self, _slice: &[T], _a: usize, _b: usize) {}
+
+    /// Called before the slice is rotated right by one.
+    fn rotate<T>(&mut self, _slice: &[T]) {}
+
+    /// Called after `count` elements were written to the slice or to a buffer outside of swaps and
+    /// rotations.
+    fn writes(&mut self, _count: usize) {}
+
+    /// Called when the element at `index` of the slice is chosen as the pivot of a partition.
+    fn pivot<T>(&mut self, _slice: &[T], _index: usize) {}
+}
+
+impl Probe for () {}
+
+/// Swaps two elements of the slice and reports the swap.
+pub(crate) fn swap<T>(slice: &mut [T], a: usize, b: usize, probe: &mut impl Probe) {
+    probe.swap(slice, a, b);
+    slice.swap(a, b);
+}
+
+/// Rotates the slice right by one, moving its last element to the front, and reports the rotation.
+pub(crate) fn rotate_right<T>(slice: &mut [T], probe: &mut impl Probe) {
+    if slice.len() > 1 {
+        probe.rotate(slice);
+        slice.rotate_right(1);
+    }
+}
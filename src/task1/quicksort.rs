@@ -1,25 +1,462 @@
+//! Recursive quicksort with selectable pivot strategies and partitioning schemes.
+
+use crate::task1::merge_sort::apply_permutation;
+use crate::task1::probe::{swap, Probe};
+use rand::Rng;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// How [`quicksort_with`] chooses the pivot of a partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pivot {
+    /// The first element, O(n²) on sorted and reversed input.
+    First,
+    /// The middle element.
+    Middle,
+    /// A uniformly random element. The running time, but not the result, differs between runs
+    /// unless [`quicksort_with_probed`] is given a seeded generator.
+    Random,
+    /// The median of the first, middle and last elements.
+    MedianOfThree,
+    /// Tukey's ninther: the median of the medians of three evenly spaced triples.
+    Ninther,
+}
+
+/// How [`quicksort_with`] splits a slice around the pivot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Partition {
+    /// Elements not greater than the pivot go left, greater ones go right.
+    TwoWay,
+    /// Dutch national flag partition into elements less than, equal to and greater than the pivot.
+    /// The equal elements are not sorted again, so many duplicates make the sort faster.
+    ThreeWay,
+}
+
+impl Pivot {
+    /// Every pivot strategy.
+    pub const ALL: [Pivot; 5] = [
+        Pivot::First,
+        Pivot::Middle,
+        Pivot::Random,
+        Pivot::MedianOfThree,
+        Pivot::Ninther,
+    ];
+
+    /// Name of the strategy, as accepted by [`str::parse`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pivot::First => "first",
+            Pivot::Middle => "middle",
+            Pivot::Random => "random",
+            Pivot::MedianOfThree => "median3",
+            Pivot::Ninther => "ninther",
+        }
+    }
+
+    /// Index of the pivot in the non-empty `slice` ordered by `compare`, `rng` picks the random
+    /// pivot.
+    fn select<T, F: FnMut(&T, &T) -> Ordering>(
+        &self,
+        slice: &[T],
+        compare: &mut F,
+        rng: &mut impl Rng,
+    ) -> usize {
+        let len = slice.len();
+        match self {
+            Pivot::First => 0,
+            Pivot::Middle => len / 2,
+            Pivot::Random => rng.gen_range(0..len),
+            Pivot::MedianOfThree => median_of_ends(slice, compare),
+            Pivot::Ninther => ninther(slice, compare),
+        }
+    }
+}
+
+impl Partition {
+    /// Every partitioning scheme.
+    pub const ALL: [Partition; 2] = [Partition::TwoWay, Partition::ThreeWay];
+
+    /// Name of the scheme, as accepted by [`str::parse`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Partition::TwoWay => "two-way",
+            Partition::ThreeWay => "three-way",
+        }
+    }
+}
+
+// index of the median of the first, middle and last elements of the non-empty slice
+pub(super) fn median_of_ends<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &[T],
+    compare: &mut F,
+) -> usize {
+    let len = slice.len();
+    median_of_three(slice, 0, len / 2, len - 1, compare)
+}
+
+// index of Tukey's ninther of the non-empty slice, the median of the ends below nine elements
+fn ninther<T, F: FnMut(&T, &T) -> Ordering>(slice: &[T], compare: &mut F) -> usize {
+    let len = slice.len();
+    if len < 9 {
+        return median_of_ends(slice, compare);
+    }
+    let (step, mid) = (len / 8, len / 2);
+    let first = median_of_three(slice, 0, step, 2 * step, compare);
+    let second = median_of_three(slice, mid - step, mid, mid + step, compare);
+    let third = median_of_three(slice, len - 1 - 2 * step, len - 1 - step, len - 1, compare);
+    median_of_three(slice, first, second, third, compare)
+}
+
+fn median_of_three<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &[T],
+    a: usize,
+    b: usize,
+    c: usize,
+    compare: &mut F,
+) -> usize {
+    let mut less = |x: usize, y: usize| compare(&slice[x], &slice[y]).is_lt();
+    if less(a, b) {
+        if less(b, c) {
+            b
+        } else if less(a, c) {
+            c
+        } else {
+            a
+        }
+    } else if less(a, c) {
+        a
+    } else if less(b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+impl fmt::Display for Pivot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl fmt::Display for Partition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Error returned when parsing an unknown pivot strategy or partitioning scheme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownStrategyError;
+
+impl fmt::Display for UnknownStrategyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown quicksort strategy")
+    }
+}
+
+impl std::error::Error for UnknownStrategyError {}
+
+impl FromStr for Pivot {
+    type Err = UnknownStrategyError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Pivot::ALL
+            .iter()
+            .copied()
+            .find(|pivot| pivot.name() == value)
+            .ok_or(UnknownStrategyError)
+    }
+}
+
+impl FromStr for Partition {
+    type Err = UnknownStrategyError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Partition::ALL
+            .iter()
+            .copied()
+            .find(|partition| partition.name() == value)
+            .ok_or(UnknownStrategyError)
+    }
+}
+
+/// Sorts the slice in place with quicksort, using the first element as the pivot.
+///
+/// The sort is not stable, see [`stable_quicksort`]. Same as [`quicksort_with`] with
+/// [`Pivot::First`] and [`Partition::TwoWay`].
 pub fn quicksort<T: Ord>(slice: &mut [T]) {
-    if slice.len() <= 1 {
-        return;
+    quicksort_with(slice, Pivot::First, Partition::TwoWay);
+}
+
+/// Same as [`quicksort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn quicksort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    quicksort_with_by(slice, Pivot::First, Partition::TwoWay, compare);
+}
+
+/// Same as [`quicksort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn quicksort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    quicksort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Sorts the slice in place with quicksort, using the given pivot strategy and partitioning scheme.
+///
+/// The sort is not stable and performs O(n²) comparisons in the worst case, e.g. with
+/// [`Pivot::First`] on sorted input. It recurses only into the smaller partition and loops on the
+/// larger one, so the recursion depth is O(log n) even then.
+pub fn quicksort_with<T: Ord>(slice: &mut [T], pivot: Pivot, partition: Partition) {
+    quicksort_with_by(slice, pivot, partition, T::cmp);
+}
+
+/// Same as [`quicksort_with`], ordering the elements with `compare` like [`slice::sort_by`].
+///
+/// This is the entry point that is not reproducible: [`Pivot::Random`] draws from
+/// [`rand::thread_rng`], so the pivots and the running time differ between runs. Use
+/// [`quicksort_with_probed`] with a seeded generator to repeat them.
+pub fn quicksort_with_by<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    pivot: Pivot,
+    partition: Partition,
+    compare: F,
+) {
+    let rng = &mut rand::thread_rng();
+    quicksort_with_probed(slice, pivot, partition, rng, compare, &mut ());
+}
+
+/// Same as [`quicksort_with_by`], drawing the [`Pivot::Random`] pivots from `rng` and reporting
+/// the pivots and swaps to `probe`.
+pub fn quicksort_with_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    pivot: Pivot,
+    partition: Partition,
+    rng: &mut impl Rng,
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    let mut select = |slice: &[T], compare: &mut F| pivot.select(slice, compare, rng);
+    sort(slice, &mut select, partition, &mut compare, probe);
+}
+
+/// Same as [`quicksort_with`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn quicksort_with_by_key<T, K: Ord, F: FnMut(&T) -> K>(
+    slice: &mut [T],
+    pivot: Pivot,
+    partition: Partition,
+    mut key: F,
+) {
+    quicksort_with_by(slice, pivot, partition, |a, b| key(a).cmp(&key(b)));
+}
+
+// `select` returns the index of the pivot, recurses into the smaller part and loops on the
+// larger one
+pub(super) fn sort<T, F: FnMut(&T, &T) -> Ordering>(
+    mut slice: &mut [T],
+    select: &mut impl FnMut(&[T], &mut F) -> usize,
+    partition: Partition,
+    compare: &mut F,
+    probe: &mut impl Probe,
+) {
+    while slice.len() > 1 {
+        let pivot_idx = select(slice, compare);
+        let (smaller, larger) = partition_around(slice, pivot_idx, partition, compare, probe);
+        sort(smaller, select, partition, compare, probe);
+        slice = larger;
+    }
+}
+
+// partitions the slice around `slice[pivot_idx]`, returns the smaller and the larger of the parts
+// left to sort
+pub(super) fn partition_around<'a, T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &'a mut [T],
+    pivot_idx: usize,
+    partition: Partition,
+    compare: &mut F,
+    probe: &mut impl Probe,
+) -> (&'a mut [T], &'a mut [T]) {
+    probe.pivot(slice, pivot_idx);
+    swap(slice, 0, pivot_idx, probe);
+    let (left, right) = match partition {
+        Partition::TwoWay => partition_two_way(slice, compare, probe),
+        Partition::ThreeWay => partition_three_way(slice, compare, probe),
+    };
+    split_parts(slice, left, right)
+}
+
+// the smaller and the larger of the parts `slice[..left]` and `slice[right..]`
+fn split_parts<T>(slice: &mut [T], left: usize, right: usize) -> (&mut [T], &mut [T]) {
+    if left < slice.len() - right {
+        let (smaller, rest) = slice.split_at_mut(left);
+        (smaller, &mut rest[right - left..])
+    } else {
+        let (rest, smaller) = slice.split_at_mut(right);
+        (smaller, &mut rest[..left])
     }
+}
+
+/// Sorts the slice in place with quicksort around Tukey's ninther, partitioning a permutation of
+/// indices into the less, equal and greater parts in their original order.
+///
+/// The sort is stable and performs O(n log n) comparisons on average, also on sorted, reversed and
+/// organ-pipe input. Like
+/// [`merge_sort`](crate::task1::merge_sort()) it applies the sorted permutation to the slice at the
+/// end, the partitions allocate index buffers of their length.
+pub fn stable_quicksort<T: Ord>(slice: &mut [T]) {
+    stable_quicksort_by(slice, T::cmp);
+}
+
+/// Same as [`stable_quicksort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn stable_quicksort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    stable_quicksort_probed(slice, compare, &mut ());
+}
+
+/// Same as [`stable_quicksort_by`], reporting the pivots and the swaps applying the permutation to
+/// `probe`.
+pub fn stable_quicksort_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    let mut order = (0..slice.len()).collect::<Vec<usize>>();
+    sort_indices(slice, &mut order, &mut compare, probe);
+    apply_permutation(slice, &mut order, probe);
+}
+
+/// Same as [`stable_quicksort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn stable_quicksort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    stable_quicksort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+// recurses into the smaller of the less and greater parts and loops on the larger one
+fn sort_indices<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &[T],
+    mut order: &mut [usize],
+    compare: &mut F,
+    probe: &mut impl Probe,
+) {
+    while order.len() > 1 {
+        let elements = order.iter().map(|&i| &slice[i]).collect::<Vec<&T>>();
+        let pivot_idx = order[ninther(&elements, &mut |a: &&T, b: &&T| compare(a, b))];
+        probe.pivot(slice, pivot_idx);
+        let pivot = &slice[pivot_idx];
+        let orderings = order
+            .iter()
+            .map(|&i| compare(&slice[i], pivot))
+            .collect::<Vec<Ordering>>();
+        let part = |ordering: Ordering| {
+            order
+                .iter()
+                .zip(&orderings)
+                .filter(move |(_, &other)| other == ordering)
+                .map(|(&i, _)| i)
+        };
+        let partitioned = part(Ordering::Less)
+            .chain(part(Ordering::Equal))
+            .chain(part(Ordering::Greater))
+            .collect::<Vec<usize>>();
+        let less = orderings.iter().filter(|ordering| ordering.is_lt()).count();
+        let greater = orderings.iter().filter(|ordering| ordering.is_gt()).count();
+        order.copy_from_slice(&partitioned);
+        let len = order.len();
+        let (smaller, larger) = split_parts(order, less, len - greater);
+        sort_indices(slice, smaller, compare, probe);
+        order = larger;
+    }
+}
+
+// partitions around `slice[0]`, returns the bounds of the pivot in its final position
+fn partition_two_way<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    compare: &mut F,
+    probe: &mut impl Probe,
+) -> (usize, usize) {
     let mut i = 1;
     let mut j = slice.len() - 1;
     loop {
-        while i < slice.len() && slice[i] <= slice[0] {
+        while i < slice.len() && compare(&slice[i], &slice[0]).is_le() {
             i += 1;
         }
-        while j > 0 && slice[j] > slice[0] {
+        while j > 0 && compare(&slice[j], &slice[0]).is_gt() {
             j -= 1;
         }
         if i >= j {
             break;
         }
-        slice.swap(i, j);
+        swap(slice, i, j, probe);
         i += 1;
         j -= 1;
     }
-    slice.swap(0, j);
+    swap(slice, 0, j, probe);
+    (j, j + 1)
+}
 
-    quicksort(&mut slice[0..j]);
-    quicksort(&mut slice[j + 1..]);
+// partitions around `slice[0]`, returns the bounds of the elements equal to the pivot
+fn partition_three_way<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    compare: &mut F,
+    probe: &mut impl Probe,
+) -> (usize, usize) {
+    // `slice[lt..i]` are equal to the pivot, `slice[gt..]` are greater
+    let (mut lt, mut i, mut gt) = (0, 1, slice.len());
+    while i < gt {
+        match compare(&slice[i], &slice[lt]) {
+            Ordering::Less => {
+                swap(slice, lt, i, probe);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                swap(slice, i, gt, probe);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    (lt, gt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending, check_stable};
+
+    #[test]
+    fn sorts_like_std_with_every_strategy() {
+        for partition in Partition::ALL {
+            for pivot in Pivot::ALL {
+                check_sort(|slice| quicksort_with(slice, pivot, partition));
+            }
+        }
+    }
+
+    #[test]
+    fn stable_quicksort_sorts_like_std() {
+        check_sort(stable_quicksort);
+    }
+
+    #[test]
+    fn stable_quicksort_is_stable() {
+        check_stable(stable_quicksort);
+    }
+
+    #[test]
+    fn sorts_by_comparator_and_key() {
+        check_sort_descending(|slice| quicksort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| quicksort_by_key(slice, |value| !*value));
+        for partition in Partition::ALL {
+            for pivot in Pivot::ALL {
+                check_sort_descending(|slice| {
+                    quicksort_with_by(slice, pivot, partition, |a, b| b.cmp(a))
+                });
+                check_sort_descending(|slice| {
+                    quicksort_with_by_key(slice, pivot, partition, |value| !*value)
+                });
+            }
+        }
+        check_sort_descending(|slice| stable_quicksort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| stable_quicksort_by_key(slice, |value| !*value));
+        check_stable(|slice| stable_quicksort_by_key(slice, |tagged| tagged.key));
+    }
 }
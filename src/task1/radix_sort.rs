@@ -0,0 +1,166 @@
+//! Least and most significant digit radix sort for integers.
+//!
+//! Both sorts use bytes as digits. The sign bit of every value is flipped before taking its digits,
+//! so negative numbers are ordered before positive ones. The `_by_key` variants sort pairs of a key
+//! and an index the same way and move the elements to their positions at the end.
+
+use crate::task1::insertion_sort::insertion_sort_probed;
+use crate::task1::merge_sort::apply_permutation;
+use crate::task1::probe::Probe;
+
+const RADIX: usize = 256;
+
+// below this length MSD radix sort finishes a bucket with insertion sort
+const MSD_INSERTION_THRESHOLD: usize = 32;
+
+fn digit(value: i32, shift: u32) -> usize {
+    ((value as u32 ^ 0x8000_0000) >> shift) as usize & (RADIX - 1)
+}
+
+// pairs of the key of every element and its index
+fn keyed<T>(slice: &[T], key: impl FnMut(&T) -> i32) -> Vec<(i32, usize)> {
+    slice.iter().map(key).zip(0..).collect()
+}
+
+// moves the elements to the order of the sorted pairs
+fn apply_keyed<T>(slice: &mut [T], pairs: &[(i32, usize)]) {
+    let mut order = pairs.iter().map(|&(_, i)| i).collect::<Vec<usize>>();
+    apply_permutation(slice, &mut order, &mut ());
+}
+
+/// Sorts the slice with LSD radix sort: a stable counting pass for each of the four bytes,
+/// starting with the least significant one.
+///
+/// Runs in O(n) time and allocates a buffer of the slice length. The sort is stable.
+pub fn lsd_radix_sort(slice: &mut [i32]) {
+    lsd_radix_sort_probed(slice, &mut ());
+}
+
+/// Same as [`lsd_radix_sort`], reporting the writes to `probe`.
+pub fn lsd_radix_sort_probed(slice: &mut [i32], probe: &mut impl Probe) {
+    lsd_sort(slice, |&value| value, probe);
+}
+
+/// Same as [`lsd_radix_sort`] for any elements, ordered by the integer key `key` extracts.
+pub fn lsd_radix_sort_by_key<T, F: FnMut(&T) -> i32>(slice: &mut [T], key: F) {
+    let mut pairs = keyed(slice, key);
+    lsd_sort(&mut pairs, |&(key, _)| key, &mut ());
+    apply_keyed(slice, &pairs);
+}
+
+fn lsd_sort<T: Copy>(slice: &mut [T], key: impl Fn(&T) -> i32, probe: &mut impl Probe) {
+    let mut buffer = slice.to_vec();
+    for shift in [0, 8, 16, 24] {
+        let mut starts = [0usize; RADIX];
+        for value in slice.iter() {
+            starts[digit(key(value), shift)] += 1;
+        }
+        let mut total = 0;
+        for start in starts.iter_mut() {
+            (total, *start) = (total + *start, total);
+        }
+        for &value in slice.iter() {
+            let bucket = &mut starts[digit(key(&value), shift)];
+            buffer[*bucket] = value;
+            *bucket += 1;
+        }
+        slice.copy_from_slice(&buffer);
+        // into the buffer and back
+        probe.writes(2 * slice.len());
+    }
+}
+
+/// Sorts the slice with MSD radix sort: distributes the values into buckets by the most significant
+/// byte and sorts every bucket recursively by the next byte.
+///
+/// Small buckets are finished with insertion sort. Runs in O(n) time and allocates a buffer of the
+/// slice length.
+pub fn msd_radix_sort(slice: &mut [i32]) {
+    msd_radix_sort_probed(slice, &mut ());
+}
+
+/// Same as [`msd_radix_sort`], reporting the writes and the swaps of the insertion sorts to
+/// `probe`.
+pub fn msd_radix_sort_probed(slice: &mut [i32], probe: &mut impl Probe) {
+    let mut buffer = slice.to_vec();
+    msd_sort(slice, &mut buffer, 24, &|&value| value, probe);
+}
+
+/// Same as [`msd_radix_sort`] for any elements, ordered by the integer key `key` extracts. The
+/// sort is stable.
+pub fn msd_radix_sort_by_key<T, F: FnMut(&T) -> i32>(slice: &mut [T], key: F) {
+    let mut pairs = keyed(slice, key);
+    let mut buffer = pairs.clone();
+    msd_sort(&mut pairs, &mut buffer, 24, &|&(key, _)| key, &mut ());
+    apply_keyed(slice, &pairs);
+}
+
+fn msd_sort<T: Copy>(
+    slice: &mut [T],
+    buffer: &mut [T],
+    shift: u32,
+    key: &impl Fn(&T) -> i32,
+    probe: &mut impl Probe,
+) {
+    if slice.len() <= MSD_INSERTION_THRESHOLD {
+        insertion_sort_probed(slice, |a, b| key(a).cmp(&key(b)), probe);
+        return;
+    }
+
+    let mut counts = [0usize; RADIX];
+    for value in slice.iter() {
+        counts[digit(key(value), shift)] += 1;
+    }
+    let mut starts = [0usize; RADIX];
+    for bucket in 1..RADIX {
+        starts[bucket] = starts[bucket - 1] + counts[bucket - 1];
+    }
+    let mut next = starts;
+    for &value in slice.iter() {
+        let bucket = &mut next[digit(key(&value), shift)];
+        buffer[*bucket] = value;
+        *bucket += 1;
+    }
+    slice.copy_from_slice(buffer);
+    probe.writes(2 * slice.len());
+
+    if shift == 0 {
+        return;
+    }
+    for bucket in 0..RADIX {
+        let range = starts[bucket]..starts[bucket] + counts[bucket];
+        if range.len() > 1 {
+            msd_sort(
+                &mut slice[range.clone()],
+                &mut buffer[range],
+                shift - 8,
+                key,
+                probe,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending, check_stable};
+
+    #[test]
+    fn sorts_like_std() {
+        check_sort(lsd_radix_sort);
+        check_sort(msd_radix_sort);
+    }
+
+    #[test]
+    fn sorts_by_key_are_stable() {
+        check_stable(|slice| lsd_radix_sort_by_key(slice, |tagged| tagged.key));
+        check_stable(|slice| msd_radix_sort_by_key(slice, |tagged| tagged.key));
+    }
+
+    #[test]
+    fn sorts_by_key() {
+        check_sort_descending(|slice| lsd_radix_sort_by_key(slice, |value| !*value));
+        check_sort_descending(|slice| msd_radix_sort_by_key(slice, |value| !*value));
+    }
+}
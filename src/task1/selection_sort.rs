@@ -1,14 +1,108 @@
+//! Selection sort.
+
+use crate::task1::probe::{rotate_right, swap, Probe};
+use std::cmp::Ordering;
+
+/// Sorts the slice in place by repeatedly swapping the minimum of the unsorted tail to its front.
+///
+/// The sort is not stable, see [`stable_selection_sort`], and always performs O(n²) comparisons.
 pub fn selection_sort<T: Ord>(slice: &mut [T]) {
+    selection_sort_by(slice, T::cmp);
+}
+
+/// Same as [`selection_sort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn selection_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    selection_sort_probed(slice, compare, &mut ());
+}
+
+/// Same as [`selection_sort_by`], reporting the swaps to `probe`.
+pub fn selection_sort_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
     let mut min_ptr = 0;
 
-    (0..(slice.len() - 1)).for_each(|unsorted_elem_ptr| {
+    (0..slice.len().saturating_sub(1)).for_each(|unsorted_elem_ptr| {
         min_ptr = unsorted_elem_ptr;
         (unsorted_elem_ptr..slice.len()).for_each(|i| {
-            slice[i].cmp(&slice[min_ptr]).is_lt().then(|| min_ptr = i);
+            compare(&slice[i], &slice[min_ptr])
+                .is_lt()
+                .then(|| min_ptr = i);
         });
         min_ptr
             .cmp(&unsorted_elem_ptr)
             .is_ne()
-            .then(|| slice.swap(unsorted_elem_ptr, min_ptr));
+            .then(|| swap(slice, unsorted_elem_ptr, min_ptr, probe));
     });
 }
+
+/// Same as [`selection_sort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn selection_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    selection_sort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Sorts the slice in place by repeatedly rotating the first minimum of the unsorted tail to its
+/// front.
+///
+/// Unlike [`selection_sort`] the sort is stable, the rotations cost O(n²) writes on top of the
+/// O(n²) comparisons.
+pub fn stable_selection_sort<T: Ord>(slice: &mut [T]) {
+    stable_selection_sort_by(slice, T::cmp);
+}
+
+/// Same as [`stable_selection_sort`], ordering the elements with `compare` like
+/// [`slice::sort_by`].
+pub fn stable_selection_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], compare: F) {
+    stable_selection_sort_probed(slice, compare, &mut ());
+}
+
+/// Same as [`stable_selection_sort_by`], reporting the rotations to `probe`.
+pub fn stable_selection_sort_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    for start in 0..slice.len().saturating_sub(1) {
+        let mut min_ptr = start;
+        for i in start + 1..slice.len() {
+            if compare(&slice[i], &slice[min_ptr]).is_lt() {
+                min_ptr = i;
+            }
+        }
+        rotate_right(&mut slice[start..=min_ptr], probe);
+    }
+}
+
+/// Same as [`stable_selection_sort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn stable_selection_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], mut key: F) {
+    stable_selection_sort_by(slice, |a, b| key(a).cmp(&key(b)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending, check_stable};
+
+    #[test]
+    fn sorts_like_std() {
+        check_sort(selection_sort);
+        check_sort(stable_selection_sort);
+    }
+
+    #[test]
+    fn stable_selection_sort_is_stable() {
+        check_stable(stable_selection_sort);
+    }
+
+    #[test]
+    fn sorts_by_comparator_and_key() {
+        check_sort_descending(|slice| selection_sort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| selection_sort_by_key(slice, |value| !*value));
+        check_sort_descending(|slice| stable_selection_sort_by(slice, |a, b| b.cmp(a)));
+        check_sort_descending(|slice| stable_selection_sort_by_key(slice, |value| !*value));
+        check_stable(|slice| stable_selection_sort_by_key(slice, |tagged| tagged.key));
+    }
+}
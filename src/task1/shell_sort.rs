@@ -0,0 +1,176 @@
+//! Shell sort with selectable gap sequences.
+
+use crate::task1::probe::{swap, Probe};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Gap sequence used by [`shell_sort`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gaps {
+    /// n/2, n/4, ..., 1 (Shell, 1959), O(n²) worst case.
+    Shell,
+    /// 1, 4, 13, 40, ... = (3^k - 1) / 2 (Knuth, 1973), O(n^(3/2)) worst case.
+    Knuth,
+    /// 1, 8, 23, 77, ... = 4^k + 3·2^(k-1) + 1 (Sedgewick, 1986), O(n^(4/3)) worst case.
+    Sedgewick,
+    /// 1, 4, 10, 23, 57, 132, 301, 701 (Ciura, 2001), extended by a factor of 2.25.
+    Ciura,
+}
+
+impl Gaps {
+    /// Every gap sequence.
+    pub const ALL: [Gaps; 4] = [Gaps::Shell, Gaps::Knuth, Gaps::Sedgewick, Gaps::Ciura];
+
+    /// Name of the sequence, as accepted by [`str::parse`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Gaps::Shell => "shell",
+            Gaps::Knuth => "knuth",
+            Gaps::Sedgewick => "sedgewick",
+            Gaps::Ciura => "ciura",
+        }
+    }
+
+    /// Gaps smaller than `len`, in decreasing order and ending with 1.
+    pub fn sequence(&self, len: usize) -> Vec<usize> {
+        let mut gaps = match self {
+            Gaps::Shell => {
+                let mut gaps = vec![];
+                let mut gap = len / 2;
+                while gap > 0 {
+                    gaps.push(gap);
+                    gap /= 2;
+                }
+                return gaps;
+            }
+            Gaps::Knuth => increasing(1, len, |_, gap| 3 * gap + 1),
+            Gaps::Sedgewick => increasing(1, len, |k, _| {
+                4usize.pow(k as u32) + 3 * 2usize.pow(k as u32 - 1) + 1
+            }),
+            Gaps::Ciura => {
+                const CIURA: [usize; 8] = [1, 4, 10, 23, 57, 132, 301, 701];
+                let mut gaps = CIURA
+                    .iter()
+                    .copied()
+                    .take_while(|&gap| gap == 1 || gap < len)
+                    .collect::<Vec<usize>>();
+                if gaps.len() == CIURA.len() {
+                    let mut gap = CIURA[CIURA.len() - 1];
+                    loop {
+                        gap = gap * 9 / 4;
+                        if gap >= len {
+                            break;
+                        }
+                        gaps.push(gap);
+                    }
+                }
+                gaps
+            }
+        };
+        gaps.reverse();
+        gaps
+    }
+}
+
+// 1 followed by `next(k, previous)` for k = 1, 2, ... while less than `len`
+fn increasing(first: usize, len: usize, next: impl Fn(usize, usize) -> usize) -> Vec<usize> {
+    let mut gaps = vec![first];
+    let mut k = 1;
+    loop {
+        let gap = next(k, gaps[gaps.len() - 1]);
+        if gap >= len {
+            return gaps;
+        }
+        gaps.push(gap);
+        k += 1;
+    }
+}
+
+impl fmt::Display for Gaps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Error returned when parsing an unknown gap sequence name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGapsError;
+
+impl fmt::Display for UnknownGapsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown gap sequence")
+    }
+}
+
+impl std::error::Error for UnknownGapsError {}
+
+impl FromStr for Gaps {
+    type Err = UnknownGapsError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Gaps::ALL
+            .iter()
+            .copied()
+            .find(|gaps| gaps.name() == value)
+            .ok_or(UnknownGapsError)
+    }
+}
+
+/// Sorts the slice in place with Shell sort: insertion sort over elements `gap` apart for every
+/// gap of the sequence, the last gap being 1.
+///
+/// The sort is not stable. Its complexity depends on `gaps`.
+pub fn shell_sort<T: Ord>(slice: &mut [T], gaps: Gaps) {
+    shell_sort_by(slice, gaps, T::cmp);
+}
+
+/// Same as [`shell_sort`], ordering the elements with `compare` like [`slice::sort_by`].
+pub fn shell_sort_by<T, F: FnMut(&T, &T) -> Ordering>(slice: &mut [T], gaps: Gaps, compare: F) {
+    shell_sort_probed(slice, gaps, compare, &mut ());
+}
+
+/// Same as [`shell_sort_by`], reporting the swaps to `probe`.
+pub fn shell_sort_probed<T, F: FnMut(&T, &T) -> Ordering>(
+    slice: &mut [T],
+    gaps: Gaps,
+    mut compare: F,
+    probe: &mut impl Probe,
+) {
+    for gap in gaps.sequence(slice.len()) {
+        for i in gap..slice.len() {
+            let mut j = i;
+            while j >= gap && compare(&slice[j], &slice[j - gap]).is_lt() {
+                swap(slice, j, j - gap, probe);
+                j -= gap;
+            }
+        }
+    }
+}
+
+/// Same as [`shell_sort`], ordering the elements by the key `key` extracts like
+/// [`slice::sort_by_key`].
+pub fn shell_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(slice: &mut [T], gaps: Gaps, mut key: F) {
+    shell_sort_by(slice, gaps, |a, b| key(a).cmp(&key(b)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::{check_sort, check_sort_descending};
+
+    #[test]
+    fn sorts_like_std_with_every_gap_sequence() {
+        for gaps in Gaps::ALL {
+            check_sort(|slice| shell_sort(slice, gaps));
+        }
+    }
+
+    #[test]
+    fn sorts_by_comparator_and_key() {
+        for gaps in Gaps::ALL {
+            check_sort_descending(|slice| shell_sort_by(slice, gaps, |a, b| b.cmp(a)));
+            check_sort_descending(|slice| shell_sort_by_key(slice, gaps, |value| !*value));
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Stability check of the sorts.
+//!
+//! A sort is stable if elements comparing equal keep their relative order. The check tags every key
+//! with its original index and sorts the pairs by key only, so the indices show whether the order
+//! of equal keys was kept.
+
+use std::cmp::Ordering;
+
+/// A key tagged with its original position, compared by the key only.
+#[derive(Debug, Clone, Copy)]
+pub struct Tagged<K> {
+    /// The compared key.
+    pub key: K,
+    /// Position of the key before sorting.
+    pub index: usize,
+}
+
+impl<K: PartialEq> PartialEq for Tagged<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Eq> Eq for Tagged<K> {}
+
+impl<K: PartialOrd> PartialOrd for Tagged<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+impl<K: Ord> Ord for Tagged<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Sorts the keys tagged with their indices with `sort` and returns whether the result is a sorted
+/// permutation of the keys in which equal keys kept their original order.
+///
+/// A sort is only shown to be unstable by keys with duplicates, so pass many equal keys.
+pub fn is_stable<K: Ord + Clone>(keys: &[K], sort: impl FnOnce(&mut [Tagged<K>])) -> bool {
+    let mut tagged = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| Tagged {
+            key: key.clone(),
+            index,
+        })
+        .collect::<Vec<Tagged<K>>>();
+    sort(&mut tagged);
+
+    let mut seen = vec![false; keys.len()];
+    let permutation = tagged.len() == keys.len()
+        && tagged.iter().all(|element| {
+            let first = element.index < keys.len()
+                && !seen[element.index]
+                && keys[element.index] == element.key;
+            if first {
+                seen[element.index] = true;
+            }
+            first
+        });
+    permutation
+        && tagged.windows(2).all(|pair| match pair[0].cmp(&pair[1]) {
+            Ordering::Less => true,
+            Ordering::Equal => pair[0].index < pair[1].index,
+            Ordering::Greater => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_stable_sort() {
+        assert!(is_stable(&[3, 1, 3, 1, 2], |slice| slice.sort()));
+        assert!(is_stable::<i32>(&[], |slice| slice.sort()));
+    }
+
+    #[test]
+    fn rejects_reordered_equal_keys() {
+        assert!(!is_stable(&[1, 1], |slice| slice.swap(0, 1)));
+    }
+
+    #[test]
+    fn rejects_unsorted_or_changed_keys() {
+        assert!(!is_stable(&[2, 1], |_| ()));
+        assert!(!is_stable(&[1, 2], |slice| slice[1] = slice[0]));
+    }
+}
@@ -0,0 +1,241 @@
+//! Step-by-step traces of the comparison sorts, e.g. to animate them with
+//! [`sort_animation`](crate::task1::sort_animation).
+//!
+//! Sorting [`Traced`] elements with the [`Tracing`] probe inside [`trace`] records, in order, every
+//! comparison between two elements of the slice, every swap and rotation and the pivots chosen by the
+//! quicksorts and introsort. Steps name the elements by their position in the slice at the time of
+//! the step. Like the counters, a trace only covers the current thread, and moves the sorts do not
+//! report, e.g. of the std sort, are missing.
+
+use crate::task1::probe::Probe;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+
+/// An operation of a sort on the traced slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// The elements at the two positions were compared.
+    Compare(usize, usize),
+    /// The elements at the two positions were swapped.
+    Swap(usize, usize),
+    /// The elements from the first to the second position were rotated right by one, the element
+    /// at the second position moving to the first.
+    Rotate(usize, usize),
+    /// The element at the position was chosen as the pivot of a partition.
+    Pivot(usize),
+}
+
+// the traced slice and the steps performed on it
+struct Recorder {
+    start: usize,
+    len: usize,
+    size: usize,
+    steps: Vec<Step>,
+}
+
+impl Recorder {
+    // position of the element in the traced slice, `None` for elements outside of it
+    fn position<T>(&self, element: &T) -> Option<usize> {
+        if size_of::<T>() != self.size || self.size == 0 {
+            return None;
+        }
+        let offset = (element as *const T as usize).checked_sub(self.start)?;
+        (offset % self.size == 0 && offset / self.size < self.len).then_some(offset / self.size)
+    }
+}
+
+thread_local! {
+    static TRACE: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+fn record(step: impl FnOnce(&Recorder) -> Option<Step>) {
+    TRACE.with(|trace| {
+        if let Some(recorder) = trace.borrow_mut().as_mut() {
+            if let Some(step) = step(recorder) {
+                recorder.steps.push(step);
+            }
+        }
+    });
+}
+
+/// Runs `sort` on the slice and returns the steps it performed on it.
+///
+/// A trace inside `sort` records its own steps, they are missing from the enclosing one.
+pub fn trace<T>(slice: &mut [Traced<T>], sort: impl FnOnce(&mut [Traced<T>])) -> Vec<Step> {
+    let recorder = Recorder {
+        start: slice.as_ptr() as usize,
+        len: slice.len(),
+        size: size_of::<Traced<T>>(),
+        steps: vec![],
+    };
+    let outer = TRACE.with(|trace| trace.replace(Some(recorder)));
+    sort(slice);
+    TRACE
+        .with(|trace| trace.replace(outer))
+        .map_or(vec![], |recorder| recorder.steps)
+}
+
+/// Element wrapper recording every comparison in [`trace`].
+#[derive(Debug, Clone, Copy)]
+pub struct Traced<T>(pub T);
+
+impl<T> Traced<T> {
+    fn record_comparison(&self, other: &Self) {
+        record(|recorder| {
+            Some(Step::Compare(
+                recorder.position(self)?,
+                recorder.position(other)?,
+            ))
+        });
+    }
+}
+
+impl<T: PartialEq> PartialEq for Traced<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.record_comparison(other);
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq> Eq for Traced<T> {}
+
+impl<T: PartialOrd> PartialOrd for Traced<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.record_comparison(other);
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T: Ord> Ord for Traced<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.record_comparison(other);
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Probe recording the swaps, rotations and pivots of a sort in [`trace`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tracing;
+
+impl Probe for Tracing {
+    fn swap<T>(&mut self, slice: &[T], a: usize, b: usize) {
+        record(|recorder| {
+            Some(Step::Swap(
+                recorder.position(&slice[a])?,
+                recorder.position(&slice[b])?,
+            ))
+        });
+    }
+
+    fn rotate<T>(&mut self, slice: &[T]) {
+        if let (Some(first), Some(last)) = (slice.first(), slice.last()) {
+            record(|recorder| {
+                Some(Step::Rotate(
+                    recorder.position(first)?,
+                    recorder.position(last)?,
+                ))
+            });
+        }
+    }
+
+    fn pivot<T>(&mut self, slice: &[T], index: usize) {
+        record(|recorder| Some(Step::Pivot(recorder.position(&slice[index])?)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task1::check::cases;
+    use crate::task1::heap_sort::heap_sort_probed;
+    use crate::task1::insertion_sort::{binary_insertion_sort_probed, insertion_sort_probed};
+    use crate::task1::introsort::introsort_with_probed;
+    use crate::task1::quicksort::{
+        quicksort_with_probed, stable_quicksort_probed, Partition, Pivot,
+    };
+    use crate::task1::selection_sort::{selection_sort_probed, stable_selection_sort_probed};
+    use crate::task1::shell_sort::{shell_sort_probed, Gaps};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // applies the moves of the steps to `values`
+    fn replay(values: &[i32], steps: &[Step]) -> Vec<i32> {
+        let mut values = values.to_vec();
+        for &step in steps {
+            match step {
+                Step::Swap(a, b) => values.swap(a, b),
+                Step::Rotate(first, last) => values[first..=last].rotate_right(1),
+                Step::Compare(..) | Step::Pivot(_) => (),
+            }
+        }
+        values
+    }
+
+    // traces `sort` on every case and checks that the steps replay to the sorted case
+    fn check_replay(sort: impl Fn(&mut [Traced<i32>])) {
+        for case in cases().into_iter().filter(|case| case.len() <= 100) {
+            let mut traced = case.iter().copied().map(Traced).collect::<Vec<_>>();
+            let steps = trace(&mut traced, &sort);
+            let sorted = traced.iter().map(|value| value.0).collect::<Vec<i32>>();
+            let mut expected = case.clone();
+            expected.sort();
+            assert_eq!(sorted, expected, "sorting {:?}", case);
+            assert_eq!(replay(&case, &steps), expected, "replaying {:?}", case);
+        }
+    }
+
+    #[test]
+    fn steps_replay_to_the_sorted_output() {
+        check_replay(|slice| insertion_sort_probed(slice, Traced::cmp, &mut Tracing));
+        check_replay(|slice| binary_insertion_sort_probed(slice, Traced::cmp, &mut Tracing));
+        check_replay(|slice| selection_sort_probed(slice, Traced::cmp, &mut Tracing));
+        check_replay(|slice| stable_selection_sort_probed(slice, Traced::cmp, &mut Tracing));
+        check_replay(|slice| heap_sort_probed(slice, Traced::cmp, &mut Tracing));
+        for gaps in Gaps::ALL {
+            check_replay(|slice| shell_sort_probed(slice, gaps, Traced::cmp, &mut Tracing));
+        }
+        for partition in Partition::ALL {
+            for pivot in Pivot::ALL {
+                check_replay(|slice| {
+                    let rng = &mut StdRng::seed_from_u64(1);
+                    quicksort_with_probed(slice, pivot, partition, rng, Traced::cmp, &mut Tracing)
+                });
+            }
+        }
+        check_replay(|slice| stable_quicksort_probed(slice, Traced::cmp, &mut Tracing));
+        check_replay(|slice| introsort_with_probed(slice, 4, Traced::cmp, &mut Tracing));
+    }
+
+    #[test]
+    fn records_the_steps_in_order() {
+        let mut traced = [2, 1].map(Traced);
+        let steps = trace(&mut traced, |slice| {
+            insertion_sort_probed(slice, Traced::cmp, &mut Tracing)
+        });
+        assert_eq!(steps, [Step::Compare(1, 0), Step::Swap(1, 0)]);
+
+        let mut traced = [3, 1, 2].map(Traced);
+        let steps = trace(&mut traced, |slice| {
+            let rng = &mut StdRng::seed_from_u64(1);
+            quicksort_with_probed(
+                slice,
+                Pivot::First,
+                Partition::TwoWay,
+                rng,
+                Traced::cmp,
+                &mut Tracing,
+            )
+        });
+        assert_eq!(steps[..2], [Step::Pivot(0), Step::Swap(0, 0)]);
+        assert_eq!(replay(&[3, 1, 2], &steps), [1, 2, 3]);
+    }
+
+    #[test]
+    fn ignores_elements_outside_the_slice() {
+        let mut traced = [Traced(1)];
+        let steps = trace(&mut traced, |_| {
+            let _ = Traced(1) < Traced(2);
+        });
+        assert_eq!(steps, []);
+    }
+}
@@ -1,3 +1,7 @@
-mod fibonacci;
-pub mod fsm;
-mod hash;
+//! Fibonacci search over sorted vectors and a simple rehashing hash set.
+
+pub mod fibonacci;
+pub mod hash;
+
+pub use fibonacci::{delete, fib_search, insert};
+pub use hash::{CustomHasSet, CustomSetExceedsCapacityError};
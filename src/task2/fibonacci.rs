@@ -1,3 +1,5 @@
+//! Fibonacci search and sorted vector maintenance built on it.
+
 use std::cmp::Ordering;
 
 struct Fib {
@@ -16,8 +18,11 @@ impl Iterator for Fib {
     }
 }
 
+/// Searches a sorted slice for `value`, probing indices along the Fibonacci sequence.
+///
+/// Returns the index of a matching element, if any, and the position where `value` can be
+/// inserted while keeping the slice sorted.
 pub fn fib_search<T: Ord>(slice: &[T], value: &T) -> (Option<usize>, usize) {
-    // Return the index of the element if it is presented in the slice and position where it can be added to.
     if slice.is_empty() {
         return (None, 0);
     }
@@ -62,12 +67,14 @@ pub fn fib_search<T: Ord>(slice: &[T], value: &T) -> (Option<usize>, usize) {
     (None, slice.len())
 }
 
+/// Inserts `value` into a sorted vector, keeping it sorted, and returns its index.
 pub fn insert<T: Ord>(vec: &mut Vec<T>, value: T) -> usize {
     let (_, idx) = fib_search(vec, &value);
     vec.insert(idx, value);
     idx
 }
 
+/// Removes one occurrence of `value` from a sorted vector and returns the index it was removed from.
 pub fn delete<T: Ord>(vec: &mut Vec<T>, value: &T) -> Option<usize> {
     if let (Some(idx), _) = fib_search(vec, value) {
         vec.remove(idx);
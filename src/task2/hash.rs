@@ -1,12 +1,28 @@
+//! Fixed-size hash set of `i32` with linear rehashing.
+
+use std::error::Error;
+use std::fmt;
+
+/// Returned when there is no free slot left for a new element.
 #[derive(Debug)]
 pub struct CustomSetExceedsCapacityError;
 
+impl fmt::Display for CustomSetExceedsCapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "set capacity exceeded")
+    }
+}
+
+impl Error for CustomSetExceedsCapacityError {}
+
+/// Hash set of `i32` with a fixed number of slots and open addressing.
 pub struct CustomHasSet {
     data: Vec<Option<i32>>,
     size: usize,
 }
 
 impl CustomHasSet {
+    /// Creates an empty set with `size` slots.
     pub fn new(size: usize) -> Self {
         Self {
             data: (0..size).map(|_| None).collect(),
@@ -14,10 +30,14 @@ impl CustomHasSet {
         }
     }
 
+    /// Returns the number of slots.
     pub fn get_size(&self) -> usize {
         self.size
     }
 
+    /// Adds `value` to the set.
+    ///
+    /// Returns `Ok(false)` if the value is already present.
     pub fn add(&mut self, value: i32) -> Result<bool, CustomSetExceedsCapacityError> {
         if self.size == 0 {
             return Err(CustomSetExceedsCapacityError);
@@ -43,6 +63,7 @@ impl CustomHasSet {
         }
     }
 
+    /// Returns `true` if the set contains `value`.
     pub fn contains(&self, value: i32) -> bool {
         if self.size == 0 {
             return false;
@@ -1,2 +1,5 @@
-pub mod fsm;
-mod substr_search;
+//! Substring search.
+
+pub mod substr_search;
+
+pub use substr_search::boyer_moor_search;
@@ -1,5 +1,11 @@
+//! Simplified Boyer-Moore substring search.
+
 use std::collections::HashMap;
 
+/// Returns the character positions of all occurrences of `template` in `string`.
+///
+/// Uses the bad character rule of the Boyer-Moore algorithm. Overlapping occurrences are
+/// reported. An empty template matches nothing.
 pub fn boyer_moor_search(string: &str, template: &str, case_sensitive: bool) -> Vec<usize> {
     let mut last_occ: HashMap<char, usize> = HashMap::new();
     let string_vec: Vec<char>;
@@ -13,6 +19,10 @@ pub fn boyer_moor_search(string: &str, template: &str, case_sensitive: bool) ->
         template_vec = template.to_lowercase().chars().collect();
     }
 
+    if template_vec.is_empty() || template_vec.len() > string_vec.len() {
+        return vec![];
+    }
+
     template_vec.iter().enumerate().for_each(|(i, char)| {
         last_occ.insert(*char, i);
     });
@@ -1,3 +1,7 @@
-pub mod fsm;
-mod parentheses;
-mod stack;
+//! A bounded stack and a parentheses balance checker.
+
+pub mod parentheses;
+pub mod stack;
+
+pub use parentheses::{parentheses, ParenthesesBalance};
+pub use stack::{Stack, StackEmptyError, StackOverflowError};
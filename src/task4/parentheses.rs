@@ -1,6 +1,20 @@
+//! Parentheses balance check.
+
 use crate::task4::stack::Stack;
 
-pub fn parentheses(string: &str) {
+/// Outcome of [`parentheses`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParenthesesBalance {
+    /// Every `(` is closed by a matching `)`.
+    Balanced,
+    /// A `)` was found without a preceding unmatched `(`.
+    UnmatchedClosing,
+    /// The string ended with this many `(` left open.
+    UnmatchedOpening(usize),
+}
+
+/// Checks whether the round parentheses in `string` are balanced. Other characters are ignored.
+pub fn parentheses(string: &str) -> ParenthesesBalance {
     let mut parentheses_stack: Stack<char> = Stack::new(string.len());
     for char in string.chars() {
         match (char, parentheses_stack.peek()) {
@@ -8,19 +22,13 @@ pub fn parentheses(string: &str) {
             (')', Some('(')) => {
                 parentheses_stack.pop().unwrap();
             }
-            (')', None) => {
-                println!("Unmatched ')' parentheses found");
-                return;
-            }
+            (')', None) => return ParenthesesBalance::UnmatchedClosing,
             _ => (),
         }
     }
 
     match parentheses_stack.get_len() {
-        0 => println!("Parentheses are balanced"),
-        _ => println!(
-            "Parentheses are not balanced, extra {} '(' parentheses found",
-            parentheses_stack.get_len(),
-        ),
-    };
+        0 => ParenthesesBalance::Balanced,
+        len => ParenthesesBalance::UnmatchedOpening(len),
+    }
 }
@@ -1,20 +1,46 @@
+//! Linked-list stack with a fixed capacity.
+
+use std::error::Error;
+use std::fmt;
+
 struct Node<T> {
     value: T,
     next: Option<Box<Node<T>>>,
 }
 
+/// LIFO stack backed by a singly linked list, holding at most `size` elements.
 pub struct Stack<T> {
     head: Option<Box<Node<T>>>,
     len: usize,
     size: usize,
 }
 
+/// Returned when pushing onto a full stack.
 #[derive(Debug)]
 pub struct StackOverflowError;
+
+impl fmt::Display for StackOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stack overflow")
+    }
+}
+
+impl Error for StackOverflowError {}
+
+/// Returned when popping from an empty stack.
 #[derive(Debug)]
 pub struct StackEmptyError;
 
+impl fmt::Display for StackEmptyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stack is empty")
+    }
+}
+
+impl Error for StackEmptyError {}
+
 impl<T> Stack<T> {
+    /// Creates an empty stack that can hold up to `size` elements.
     pub fn new(size: usize) -> Self {
         Stack {
             head: None,
@@ -23,10 +49,12 @@ impl<T> Stack<T> {
         }
     }
 
+    /// Returns the number of elements on the stack.
     pub fn get_len(&self) -> usize {
         self.len
     }
 
+    /// Returns the top element without removing it.
     pub fn peek(&self) -> Option<&T> {
         match &self.head {
             Some(node) => Some(&node.value),
@@ -34,6 +62,7 @@ impl<T> Stack<T> {
         }
     }
 
+    /// Removes and returns the top element.
     pub fn pop(&mut self) -> Result<T, StackEmptyError> {
         if self.len == 0 {
             return Err(StackEmptyError);
@@ -44,6 +73,7 @@ impl<T> Stack<T> {
         Ok(head_node.value)
     }
 
+    /// Pushes `value` on top of the stack.
     pub fn push(&mut self, value: T) -> Result<(), StackOverflowError> {
         if self.len == self.size {
             return Err(StackOverflowError);
@@ -1,2 +1,5 @@
-mod fractal;
-pub mod fsm;
+//! Fractal drawing.
+
+pub mod fractal;
+
+pub use fractal::{draw_koch_curve, koch_curve};
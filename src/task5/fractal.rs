@@ -1,4 +1,8 @@
+//! Koch curve rendering.
+
 use std::f64::consts::PI;
+use std::io;
+use std::path::Path as FsPath;
 use svg::node::element::path::Command::{Line, Move};
 use svg::node::element::path::Position::Absolute;
 use svg::node::element::path::{Command, Data, Parameters};
@@ -70,7 +74,8 @@ fn get_curve_points(depth: usize, length: f64, alpha: f64, start: Point, end: Po
     v
 }
 
-pub fn draw_koch_curve(depth: usize) {
+/// Builds an SVG document with a Koch curve of the given recursion depth.
+pub fn koch_curve(depth: usize) -> Document {
     let init_length = 10. * 3_f64.powi(depth as i32 + 1);
     let start = Point::new(0., init_length / 3.);
     let end = Point::new(start.x + init_length, init_length / 3.);
@@ -78,10 +83,15 @@ pub fn draw_koch_curve(depth: usize) {
         get_curve_points(depth, init_length / 3., 0., start, end),
         start,
         init_length,
-    );
+    )
+}
+
+/// Renders a Koch curve of the given recursion depth and saves it as SVG to `path`.
+pub fn draw_koch_curve<P: AsRef<FsPath>>(depth: usize, path: P) -> io::Result<()> {
+    svg::save(path, &koch_curve(depth))
 }
 
-fn draw(points: Vec<Point>, start: Point, init_length: f64) {
+fn draw(points: Vec<Point>, start: Point, init_length: f64) -> Document {
     let mut commands = vec![Move(Absolute, Parameters::from((start.x, start.y)))];
     commands.append(
         &mut points
@@ -95,8 +105,7 @@ fn draw(points: Vec<Point>, start: Point, init_length: f64) {
         .set("stroke", "black")
         .set("vector-effect", "non-scaling-stroke")
         .set("d", data);
-    let document = Document::new()
+    Document::new()
         .set("viewBox", (0, 0, init_length, 2. * init_length / 3.))
-        .add(path);
-    svg::save("koch.svg", &document).unwrap();
+        .add(path)
 }
@@ -0,0 +1,447 @@
+use crate::cli::{generate, parallelism, random_array, seed, Arg, CliError, Options, ARRAY_ARGS};
+use crate::common::{Io, LineReader, Res};
+use crate::distribution::Distribution;
+use crate::fsm::task1::animation;
+use crate::fsm::task1::animation::Animation;
+use crate::fsm::task1::export::{report_export, save_benchmark, save_sweep};
+use crate::fsm::task1::external::{
+    external_sort, ExternalJob, Memory, DEFAULT_MEMORY, DEFAULT_SORT,
+};
+use crate::fsm::task1::sweep::{
+    geometric_sizes, Sweep, DEFAULT_BUDGET, DEFAULT_RANGE, DEFAULT_RUNS, DEFAULT_SIZES,
+    DEFAULT_STEPS, MAX_SIZE, MAX_STEPS,
+};
+use crate::fsm::task1::{
+    algorithms, select_algorithms, Parallelism, SortBenchmark, SortStateMachine, DEFAULT_WARMUP,
+};
+use crate::fsm::task2::{
+    run_add, run_contains, run_create, run_search as run_fib_search, Task2StateMachine,
+};
+use crate::fsm::task3::{run_search, SimplifiedBoyerMoorStateMachine};
+use crate::fsm::task4::{run_check, CheckParenthesesStateMachine};
+use crate::fsm::task5::{run_draw, KochCurveDrawerStateMachine, DEFAULT_OUTPUT};
+use crate::machine::StateMachine;
+use course_project::task1::Gaps;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::io;
+use std::io::Write;
+
+/// A task of the main menu together with its command line subcommands.
+pub struct Task<R: LineReader, W: Write> {
+    /// Identifier of the task, accepted by the main menu instead of its number.
+    pub name: &'static str,
+    /// Shown in the main menu.
+    pub description: &'static str,
+    /// Runs the menu of the task.
+    pub start: fn(&mut Io<R, W>) -> Res<()>,
+    pub subcommands: Vec<Subcommand<W>>,
+}
+
+/// Non-interactive command running (a part of) a task.
+pub struct Subcommand<W: Write> {
+    pub name: &'static str,
+    /// Options of the command, parsed before `run` and shown in the usage.
+    pub args: &'static [Arg],
+    pub run: fn(&mut W, &Options) -> Result<(), CliError>,
+}
+
+/// Every task, in the order of the main menu. Tasks are numbered from 1.
+///
+/// To add a task, add its entry here and its subcommands below.
+pub fn tasks<R: LineReader, W: Write>() -> Vec<Task<R, W>> {
+    vec![
+        Task {
+            name: "sort",
+            description: "Sort",
+            start: |io| SortStateMachine::new(io).start(),
+            subcommands: vec![
+                Subcommand {
+                    name: "sort",
+                    args: &[
+                        ARRAY_ARGS,
+                        Arg::Optional("iterations", "<k>"),
+                        Arg::Optional("warmup", "<k>"),
+                        Arg::Optional("seed", "<n>"),
+                        Arg::Optional("gaps", "<shell|knuth|sedgewick|ciura>"),
+                        Arg::Optional("algorithms", "<name>[,<name>...]"),
+                        Arg::Optional("threads", "<n>"),
+                        Arg::Optional("cutoff", "<n>"),
+                        Arg::Optional("distribution", "<name>[:<n>]"),
+                        Arg::Flag("count"),
+                        Arg::Flag("records"),
+                        Arg::Optional("export", "<prefix>"),
+                    ],
+                    run: run_sort,
+                },
+                Subcommand {
+                    name: "sweep",
+                    args: &[
+                        Arg::Optional("from", "<n>"),
+                        Arg::Optional("to", "<n>"),
+                        Arg::Optional("steps", "<k>"),
+                        Arg::Optional("budget", "<seconds>"),
+                        Arg::Optional("runs", "<k>"),
+                        Arg::Optional("left", "<l>"),
+                        Arg::Optional("right", "<r>"),
+                        Arg::Optional("distribution", "<name>[:<n>]"),
+                        Arg::Optional("seed", "<n>"),
+                        Arg::Optional("gaps", "<name>"),
+                        Arg::Optional("threads", "<n>"),
+                        Arg::Optional("cutoff", "<n>"),
+                        Arg::Optional("algorithms", "<name>[,<name>...]"),
+                        Arg::Optional("export", "<prefix>"),
+                    ],
+                    run: run_sweep,
+                },
+                Subcommand {
+                    name: "external",
+                    args: &[
+                        Arg::Required("input", "<file>"),
+                        Arg::Required("output", "<file>"),
+                        Arg::Optional("memory", "<bytes>[K|M|G]"),
+                        Arg::Optional("algorithm", "<name>"),
+                        Arg::Optional("input-format", "<text|binary>"),
+                        Arg::Optional("output-format", "<text|binary>"),
+                        Arg::Optional("seed", "<n>"),
+                    ],
+                    run: run_external,
+                },
+                Subcommand {
+                    name: "animate",
+                    args: &[
+                        Arg::Required("algorithm", "<name>"),
+                        Arg::Optional("size", "<n>"),
+                        Arg::Optional("left", "<l>"),
+                        Arg::Optional("right", "<r>"),
+                        Arg::Optional("distribution", "<name>[:<n>]"),
+                        Arg::Optional("input", "<file|->"),
+                        Arg::Optional("seed", "<n>"),
+                        Arg::Optional("gaps", "<name>"),
+                        Arg::Optional("output", "<file>"),
+                        Arg::Optional("step", "<seconds>"),
+                    ],
+                    run: run_animate,
+                },
+            ],
+        },
+        Task {
+            name: "fibonacci",
+            description: "Fibonacci search and simple rehash",
+            start: |io| Task2StateMachine::new(io).start(),
+            subcommands: vec![
+                Subcommand {
+                    name: "fibsearch",
+                    args: &[
+                        ARRAY_ARGS,
+                        Arg::Required("value", "<v>[,<v>...]"),
+                        Arg::Optional("seed", "<n>"),
+                    ],
+                    run: run_fibsearch,
+                },
+                Subcommand {
+                    name: "hashset",
+                    args: &[
+                        Arg::Required("size", "<n>"),
+                        Arg::Optional("add", "<v>[,<v>...]"),
+                        Arg::Optional("search", "<v>[,<v>...]"),
+                    ],
+                    run: run_hashset,
+                },
+            ],
+        },
+        Task {
+            name: "boyer-moore",
+            description: "Simplified Boyer-Moor search",
+            start: |io| SimplifiedBoyerMoorStateMachine::new(io).start(),
+            subcommands: vec![Subcommand {
+                name: "search",
+                args: &[
+                    Arg::Required("string", "<s>"),
+                    Arg::Required("pattern", "<p>"),
+                    Arg::Flag("case-insensitive"),
+                ],
+                run: run_substr_search,
+            }],
+        },
+        Task {
+            name: "parentheses",
+            description: "Check parentheses balance",
+            start: |io| CheckParenthesesStateMachine::new(io).start(),
+            subcommands: vec![Subcommand {
+                name: "parens",
+                args: &[Arg::Required("string", "<s>")],
+                run: run_parens,
+            }],
+        },
+        Task {
+            name: "koch",
+            description: "Draw Koch curve",
+            start: |io| KochCurveDrawerStateMachine::new(io).start(),
+            subcommands: vec![Subcommand {
+                name: "koch",
+                args: &[
+                    Arg::Required("depth", "<d>"),
+                    Arg::Optional("output", "<file>"),
+                ],
+                run: run_koch,
+            }],
+        },
+    ]
+}
+
+/// Subcommands of all tasks.
+pub fn subcommands<W: Write>() -> Vec<Subcommand<W>> {
+    // the menu entry points are not used, so the reader type does not matter
+    tasks::<io::Empty, W>()
+        .into_iter()
+        .flat_map(|task| task.subcommands)
+        .collect()
+}
+
+// the runners of the subcommands get the options parsed with the `args` of their entry
+
+fn run_sort(out: &mut impl Write, options: &Options) -> Result<(), CliError> {
+    let format = options.format()?;
+    let iterations = options.get_or::<usize>("iterations", 1)?;
+    let gaps = options.get_or::<Gaps>("gaps", Gaps::Ciura)?;
+    let patterns = options.get_list::<String>("algorithms")?;
+    let algorithms = select_algorithms(
+        algorithms(gaps, parallelism(options)?, seed(options)?),
+        &patterns,
+    )
+    .map_err(|unknown| CliError::Usage(format!("Unknown algorithm '{}'", unknown)))?;
+    let mut benchmark = SortBenchmark::new(generate(options)?, algorithms)
+        .with_counting(options.has_flag("count"))
+        .with_warmup(options.get_or::<usize>("warmup", DEFAULT_WARMUP)?)
+        .with_records(options.has_flag("records"));
+    (0..iterations).for_each(|_| benchmark.work(out, format));
+    benchmark.print_stat(out, format);
+    if let Some(prefix) = options.get_optional::<String>("export")? {
+        let saved = save_benchmark(&benchmark, &prefix);
+        report_export(out, format, &prefix, saved).map_err(|_| CliError::Reported)?;
+    }
+    Ok(())
+}
+
+fn run_sweep(out: &mut impl Write, options: &Options) -> Result<(), CliError> {
+    let format = options.format()?;
+    let from = options.get_or::<usize>("from", DEFAULT_SIZES.0)?;
+    let to = options.get_or::<usize>("to", DEFAULT_SIZES.1)?;
+    let left = options.get_or::<i32>("left", DEFAULT_RANGE.0)?;
+    let right = options.get_or::<i32>("right", DEFAULT_RANGE.1)?;
+    let steps = options.get_or::<usize>("steps", DEFAULT_STEPS)?;
+    let budget = options.get_or::<f64>("budget", DEFAULT_BUDGET)?;
+    if from == 0 || to < from || to > MAX_SIZE {
+        return Err(CliError::Usage(format!(
+            "Sizes must be between 1 and {} and '--to' not less than '--from'",
+            MAX_SIZE
+        )));
+    }
+    if steps == 0 || steps > MAX_STEPS {
+        return Err(CliError::Usage(format!(
+            "Steps must be between 1 and {}",
+            MAX_STEPS
+        )));
+    }
+    if right <= left {
+        return Err(CliError::Usage(
+            "Right must be greater than left".to_string(),
+        ));
+    }
+    if budget.is_nan() || budget <= 0.0 {
+        return Err(CliError::Usage("Budget must be positive".to_string()));
+    }
+    let gaps = options.get_or::<Gaps>("gaps", Gaps::Ciura)?;
+    let patterns = options.get_list::<String>("algorithms")?;
+    let algorithms = select_algorithms(
+        algorithms(gaps, parallelism(options)?, seed(options)?),
+        &patterns,
+    )
+    .map_err(|unknown| CliError::Usage(format!("Unknown algorithm '{}'", unknown)))?;
+    let sweep = Sweep {
+        sizes: geometric_sizes(from, to, steps),
+        left,
+        right,
+        distribution: options.get_or::<Distribution>("distribution", Distribution::Uniform)?,
+        budget,
+        runs: options.get_or::<usize>("runs", DEFAULT_RUNS)?,
+    };
+    let mut rng = StdRng::seed_from_u64(seed(options)?);
+    let result = sweep.run(out, format, &algorithms, &mut rng);
+    if let Some(prefix) = options.get_optional::<String>("export")? {
+        let saved = save_sweep(&result, &prefix);
+        report_export(out, format, &prefix, saved).map_err(|_| CliError::Reported)?;
+    }
+    Ok(())
+}
+
+fn run_external(out: &mut impl Write, options: &Options) -> Result<(), CliError> {
+    let format = options.format()?;
+    let input = options.get::<String>("input")?;
+    let output = options.get::<String>("output")?;
+    let mut job = ExternalJob::new(
+        &input,
+        &output,
+        options.get_or::<Memory>("memory", DEFAULT_MEMORY)?,
+    );
+    job.input_format = options.get_or("input-format", job.input_format)?;
+    job.output_format = options.get_or("output-format", job.output_format)?;
+    let name = options.get_or::<String>("algorithm", DEFAULT_SORT.to_string())?;
+    let algorithm = algorithms(Gaps::Ciura, Parallelism::default(), seed(options)?)
+        .into_iter()
+        .find(|algorithm| algorithm.name == name)
+        .ok_or_else(|| CliError::Usage(format!("Unknown algorithm '{}'", name)))?;
+    external_sort(out, format, &job, algorithm)
+        .map(|_| ())
+        .map_err(|_| CliError::Reported)
+}
+
+fn run_animate(out: &mut impl Write, options: &Options) -> Result<(), CliError> {
+    let format = options.format()?;
+    let gaps = options.get_or::<Gaps>("gaps", Gaps::Ciura)?;
+    let animated = Animation::of(
+        algorithms(gaps, Parallelism::default(), seed(options)?),
+        &options.get::<String>("algorithm")?,
+    )
+    .map_err(CliError::Usage)?;
+    let vec = match options.get_optional::<String>("input")? {
+        Some(_) => generate(options)?,
+        None => random_array(
+            options,
+            options.get_or::<usize>("size", animation::DEFAULT_SIZE)?,
+            options.get_or::<i32>("left", animation::DEFAULT_RANGE.0)?,
+            options.get_or::<i32>("right", animation::DEFAULT_RANGE.1)?,
+        )?,
+    };
+    if vec.len() > animation::MAX_SIZE {
+        return Err(CliError::Usage(format!(
+            "At most {} elements can be animated",
+            animation::MAX_SIZE
+        )));
+    }
+    let step = options.get_or::<f64>("step", animation::DEFAULT_STEP_SECONDS)?;
+    if !step.is_finite() || step <= 0.0 {
+        return Err(CliError::Usage("Step must be positive".to_string()));
+    }
+    let output = options.get_or::<String>("output", animation::DEFAULT_OUTPUT.to_string())?;
+    animated
+        .run(out, format, &vec, &output, step)
+        .map_err(|_| CliError::Reported)
+}
+
+fn run_fibsearch(out: &mut impl Write, options: &Options) -> Result<(), CliError> {
+    let format = options.format()?;
+    let values = options.get_list::<i32>("value")?;
+    if values.is_empty() {
+        return Err(CliError::Usage("Option '--value' is required".to_string()));
+    }
+    let mut vec = generate(options)?;
+    vec.sort_unstable();
+    values
+        .iter()
+        .for_each(|value| run_fib_search(out, format, &vec, *value));
+    Ok(())
+}
+
+fn run_hashset(out: &mut impl Write, options: &Options) -> Result<(), CliError> {
+    let format = options.format()?;
+    let size = options.get::<usize>("size")?;
+    let to_add = options.get_list::<i32>("add")?;
+    let to_search = options.get_list::<i32>("search")?;
+
+    let mut set = run_create(out, format, size);
+    to_add
+        .iter()
+        .for_each(|value| run_add(out, format, &mut set, *value));
+    to_search
+        .iter()
+        .for_each(|value| run_contains(out, format, &set, *value));
+    Ok(())
+}
+
+fn run_substr_search(out: &mut impl Write, options: &Options) -> Result<(), CliError> {
+    let string = options.get::<String>("string")?;
+    let pattern = options.get::<String>("pattern")?;
+    run_search(
+        out,
+        options.format()?,
+        &string,
+        &pattern,
+        options.has_flag("case-insensitive"),
+    );
+    Ok(())
+}
+
+fn run_parens(out: &mut impl Write, options: &Options) -> Result<(), CliError> {
+    run_check(out, options.format()?, &options.get::<String>("string")?);
+    Ok(())
+}
+
+fn run_koch(out: &mut impl Write, options: &Options) -> Result<(), CliError> {
+    let output = options.get_or::<String>("output", DEFAULT_OUTPUT.to_string())?;
+    run_draw(
+        out,
+        options.format()?,
+        options.get::<usize>("depth")?,
+        &output,
+    )
+    .map_err(|_| CliError::Reported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{run, usage};
+
+    fn run_args(args: &[&str]) -> (String, Result<(), CliError>) {
+        let mut out = vec![];
+        let args = args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>();
+        let res = run(&mut out, &args);
+        (String::from_utf8(out).unwrap(), res)
+    }
+
+    #[test]
+    fn lists_every_subcommand_once() {
+        let names = subcommands::<io::Sink>()
+            .iter()
+            .map(|command| command.name)
+            .collect::<Vec<&str>>();
+        for name in &names {
+            assert_eq!(names.iter().filter(|other| *other == name).count(), 1);
+            assert!(usage().contains(&format!("\n  {:<10} ", name)));
+        }
+    }
+
+    #[test]
+    fn subcommands_get_the_options_of_their_entry() {
+        let (out, res) = run_args(&["parens", "--string", "(()", "--format", "json"]);
+        assert!(res.is_ok());
+        assert!(
+            out.starts_with(r#"{"task":"parens","operation":"check","inputs":{"string":"(()"}"#)
+        );
+
+        let (out, res) = run_args(&["hashset", "--size", "2", "--add", "1,3", "--search", "3"]);
+        assert!(res.is_ok());
+        assert_eq!(
+            out,
+            "Set with size 2 created\nElement 1 added\nSet is full\nElement 3 not found\n"
+        );
+
+        let (_, res) = run_args(&["parens", "--depth", "1"]);
+        assert!(
+            matches!(res, Err(CliError::Usage(message)) if message == "Unknown option '--depth'")
+        );
+        let (_, res) = run_args(&["koch"]);
+        assert!(
+            matches!(res, Err(CliError::Usage(message)) if message == "Option '--depth' is required")
+        );
+        let (_, res) = run_args(&["search", "--string", "abc", "--case-insensitive", "x"]);
+        assert!(
+            matches!(res, Err(CliError::Usage(message)) if message == "Unexpected argument 'x'")
+        );
+    }
+}